@@ -0,0 +1,166 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        NoirFunction, NoirStruct, NoirTrait, NoirTraitImpl, NoirTypeAlias, TraitItem, TypeImpl,
+        UnresolvedGeneric, UnresolvedGenerics, UnresolvedType, UnresolvedTypeExpression, Visitor,
+    },
+    ParsedModule,
+};
+
+/// Returns the name and span of every use of a numeric generic as the length of an array, string
+/// or format string type in `module`, as in `[Field; N]` or `str<N + 1>`, in the order they
+/// appear in the source.
+///
+/// Constant lengths such as the `32` in `[u8; 32]` aren't returned, nor are names which don't
+/// refer to a numeric generic of the enclosing function, struct, trait, impl or type alias (such
+/// as a global).
+pub fn collect_array_length_generics(module: &ParsedModule) -> Vec<(String, Span)> {
+    let mut collector = ArrayLengthCollector::default();
+    module.accept(&mut collector);
+
+    let mut lengths = collector.lengths;
+    lengths.sort_by_key(|(_, span)| span.start());
+    lengths
+}
+
+#[derive(Default)]
+struct ArrayLengthCollector {
+    /// The names of the numeric generics in scope.
+    numeric_generics: Vec<String>,
+    lengths: Vec<(String, Span)>,
+}
+
+impl ArrayLengthCollector {
+    /// Runs `f` with the numeric generics among `generics` brought into scope.
+    fn with_generics(&mut self, generics: &UnresolvedGenerics, f: impl FnOnce(&mut Self)) {
+        let outer_generics = self.numeric_generics.len();
+        for generic in generics {
+            if let UnresolvedGeneric::Numeric { ident, .. } = generic {
+                self.numeric_generics.push(ident.0.contents.clone());
+            }
+        }
+        f(self);
+        self.numeric_generics.truncate(outer_generics);
+    }
+
+    fn collect_length(&mut self, length: &UnresolvedTypeExpression) {
+        match length {
+            UnresolvedTypeExpression::Variable(path) => {
+                let Some(ident) = path.as_ident() else {
+                    return;
+                };
+                if self.numeric_generics.contains(&ident.0.contents) {
+                    self.lengths.push((ident.0.contents.clone(), ident.span()));
+                }
+            }
+            UnresolvedTypeExpression::BinaryOperation(lhs, _, rhs, _) => {
+                self.collect_length(lhs);
+                self.collect_length(rhs);
+            }
+            UnresolvedTypeExpression::Constant(..) | UnresolvedTypeExpression::AsTraitPath(_) => (),
+        }
+    }
+}
+
+impl Visitor for ArrayLengthCollector {
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        self.with_generics(&function.def.generics, |this| function.accept_children(this));
+        false
+    }
+
+    fn visit_noir_struct(&mut self, noir_struct: &NoirStruct, _: Span) -> bool {
+        self.with_generics(&noir_struct.generics, |this| noir_struct.accept_children(this));
+        false
+    }
+
+    fn visit_noir_trait(&mut self, noir_trait: &NoirTrait, _: Span) -> bool {
+        self.with_generics(&noir_trait.generics, |this| noir_trait.accept_children(this));
+        false
+    }
+
+    fn visit_trait_item(&mut self, item: &TraitItem) -> bool {
+        let TraitItem::Function { generics, .. } = item else {
+            return true;
+        };
+        self.with_generics(generics, |this| item.accept_children(this));
+        false
+    }
+
+    fn visit_noir_trait_impl(&mut self, trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        self.with_generics(&trait_impl.impl_generics, |this| trait_impl.accept_children(this));
+        false
+    }
+
+    fn visit_type_impl(&mut self, type_impl: &TypeImpl, _: Span) -> bool {
+        self.with_generics(&type_impl.generics, |this| type_impl.accept_children(this));
+        false
+    }
+
+    fn visit_noir_type_alias(&mut self, type_alias: &NoirTypeAlias, _: Span) -> bool {
+        self.with_generics(&type_alias.generics, |this| type_alias.accept_children(this));
+        false
+    }
+
+    fn visit_array_type(
+        &mut self,
+        length: &UnresolvedTypeExpression,
+        _: &UnresolvedType,
+        _: Span,
+    ) -> bool {
+        self.collect_length(length);
+        true
+    }
+
+    fn visit_string_type(&mut self, length: &UnresolvedTypeExpression, _: Span) {
+        self.collect_length(length);
+    }
+
+    fn visit_format_string_type(
+        &mut self,
+        length: &UnresolvedTypeExpression,
+        _: &UnresolvedType,
+        _: Span,
+    ) -> bool {
+        self.collect_length(length);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::collect_array_length_generics;
+
+    #[test]
+    fn collects_generic_lengths_only() {
+        let src = "
+        global SIZE: u32 = 3;
+
+        struct Foo<let N: u32, T> {
+            a: [T; N],
+            b: [u8; 32],
+            c: str<N>,
+        }
+
+        impl<let K: u32> Foo<K, Field> {
+            fn bar<let L: u32>(self, xs: [[Field; L]; K]) -> [Field; SIZE] {
+                let ys: [Field; L] = [0; L];
+                [0; SIZE]
+            }
+        }
+
+        fn main<let M: u32>(x: fmtstr<M, (Field, Field)>, y: [Field; 4]) -> pub [u8; M + 1] {}
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let lengths = collect_array_length_generics(&module);
+        let names: Vec<_> = lengths.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["N", "N", "L", "K", "L", "M", "M"]);
+
+        let (_, span) = &lengths[0];
+        assert_eq!(span.start() as usize, src.find("N],").unwrap());
+    }
+}
@@ -0,0 +1,159 @@
+use std::collections::{BTreeSet, HashMap};
+
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        BlockExpression, FunctionReturnType, Ident, NoirFunction, UnresolvedGenerics,
+        UnresolvedTraitConstraint, UnresolvedType, Visitor,
+    },
+    ParsedModule,
+};
+
+use super::call_sites::CallCollector;
+
+/// The functions of a module along with the functions each of them calls, built by
+/// [build_call_graph]. Functions are identified by name.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    /// The name of every function, in the order they're first declared in.
+    functions: Vec<String>,
+    callees: HashMap<String, BTreeSet<String>>,
+}
+
+impl CallGraph {
+    /// Returns the name of every function, in the order they're first declared in.
+    pub fn functions(&self) -> &[String] {
+        &self.functions
+    }
+
+    /// Returns the names of the functions called by `function`, in alphabetical order, or `None`
+    /// if there is no function named `function`.
+    pub fn callees(&self, function: &str) -> Option<&BTreeSet<String>> {
+        self.callees.get(function)
+    }
+
+    /// Returns the names of the functions which call `function`, in the order they're declared
+    /// in.
+    pub fn callers(&self, function: &str) -> Vec<&str> {
+        let callers = self.functions.iter().filter(|caller| self.calls(caller, function));
+        callers.map(String::as_str).collect()
+    }
+
+    /// Returns whether `caller` calls `callee`.
+    pub fn calls(&self, caller: &str, callee: &str) -> bool {
+        self.callees(caller).map_or(false, |callees| callees.contains(callee))
+    }
+}
+
+/// Returns the call graph of `module`, whose functions are those of `module` including functions
+/// in impls, trait impls and submodules as well as trait methods with a default body.
+///
+/// As names aren't resolved, functions sharing a name are merged into one, and calls are matched
+/// on the final segment of the callee's path or on the method name (so `foo(x)`, `bar::foo(x)`
+/// and `x.foo()` are all calls to `foo`). Calls to functions which aren't declared in `module`,
+/// and calls through function values (such as lambdas passed as arguments), aren't included.
+pub fn build_call_graph(module: &ParsedModule) -> CallGraph {
+    let mut collector = FunctionCallCollector::default();
+    module.accept(&mut collector);
+
+    let mut call_graph = CallGraph::default();
+    for (name, _) in &collector.functions {
+        if !call_graph.callees.contains_key(name) {
+            call_graph.functions.push(name.clone());
+            call_graph.callees.insert(name.clone(), BTreeSet::new());
+        }
+    }
+    for (name, callees) in collector.functions {
+        let declared_callees =
+            callees.into_iter().filter(|callee| call_graph.callees.contains_key(callee));
+        let declared_callees: Vec<_> = declared_callees.collect();
+        call_graph.callees.get_mut(&name).expect("Inserted above").extend(declared_callees);
+    }
+    call_graph
+}
+
+/// Collects the name of every function along with the names of everything it calls.
+#[derive(Default)]
+struct FunctionCallCollector {
+    functions: Vec<(String, Vec<String>)>,
+}
+
+impl FunctionCallCollector {
+    fn push(&mut self, name: &Ident, calls: CallCollector) {
+        let callees = calls.calls.into_iter().map(|(callee, _)| callee).collect();
+        self.functions.push((name.0.contents.clone(), callees));
+    }
+}
+
+impl Visitor for FunctionCallCollector {
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        let mut calls = CallCollector::default();
+        function.accept_children(&mut calls);
+        self.push(&function.def.name, calls);
+        false
+    }
+
+    fn visit_trait_item_function(
+        &mut self,
+        name: &Ident,
+        _generics: &UnresolvedGenerics,
+        _parameters: &[(Ident, UnresolvedType)],
+        _return_type: &FunctionReturnType,
+        _where_clause: &[UnresolvedTraitConstraint],
+        body: &Option<BlockExpression>,
+    ) -> bool {
+        if let Some(body) = body {
+            let mut calls = CallCollector::default();
+            body.accept(None, &mut calls);
+            self.push(name, calls);
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::build_call_graph;
+
+    #[test]
+    fn builds_edges_from_calls() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let y = foo(x) + std::hash::pedersen_hash([x]);
+            bar::baz(y).qux()
+        }
+
+        fn foo(x: Field) -> Field { x.qux() + foo(x - 1) }
+
+        mod bar {
+            fn baz(x: Field) -> Field { x }
+        }
+
+        impl Foo {
+            fn qux(self) -> Field { self }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let call_graph = build_call_graph(&module);
+        assert_eq!(call_graph.functions(), ["main", "foo", "baz", "qux"]);
+
+        let callees = |name: &str| -> Vec<_> {
+            call_graph.callees(name).unwrap().iter().map(String::as_str).collect()
+        };
+        assert_eq!(callees("main"), vec!["baz", "foo", "qux"]);
+        assert_eq!(callees("foo"), vec!["foo", "qux"]);
+        assert!(callees("baz").is_empty());
+        assert!(callees("qux").is_empty());
+        assert!(call_graph.callees("pedersen_hash").is_none());
+
+        assert_eq!(call_graph.callers("qux"), vec!["main", "foo"]);
+        assert!(call_graph.callers("main").is_empty());
+        assert!(call_graph.calls("foo", "foo"));
+        assert!(!call_graph.calls("baz", "foo"));
+    }
+}
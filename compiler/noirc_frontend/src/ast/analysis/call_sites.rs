@@ -0,0 +1,129 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{CallExpression, ExpressionKind, MethodCallExpression, Visitor},
+    ParsedModule,
+};
+
+/// A call to a function, found by [find_call_sites_with_kind].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CallSite {
+    /// The span of the whole call expression.
+    pub span: Span,
+    pub kind: CallKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CallKind {
+    /// A call through a path, such as `foo(x)`, `foo::bar(x)` or `<T as Foo>::bar(x)`.
+    Function,
+    /// A method call, such as `x.foo()`.
+    Method,
+}
+
+/// Returns the span of every call expression in `module` which calls a function or method
+/// named `name`, in the order they appear in the source.
+///
+/// Only the final segment of the callee's path is compared, so `foo(x)`, `bar::foo(x)`,
+/// `Type::foo(x)` and `x.foo()` are all call sites of `foo`. Calls through a variable holding a
+/// function (e.g. a lambda) are matched on the variable's name.
+pub fn find_call_sites(module: &ParsedModule, name: &str) -> Vec<Span> {
+    find_call_sites_with_kind(module, name).into_iter().map(|call_site| call_site.span).collect()
+}
+
+/// Like [find_call_sites], but also returns whether each call is a function or a method call.
+pub fn find_call_sites_with_kind(module: &ParsedModule, name: &str) -> Vec<CallSite> {
+    let mut collector = CallCollector::default();
+    module.accept(&mut collector);
+
+    let mut call_sites: Vec<_> = collector
+        .calls
+        .into_iter()
+        .filter(|(callee, _)| callee == name)
+        .map(|(_, call_site)| call_site)
+        .collect();
+    call_sites.sort_by_key(|call_site| call_site.span.start());
+    call_sites
+}
+
+/// Collects every call expression visited, along with the name of the function or method it
+/// calls (the final segment of its path).
+#[derive(Default)]
+pub(super) struct CallCollector {
+    pub(super) calls: Vec<(String, CallSite)>,
+}
+
+impl Visitor for CallCollector {
+    fn visit_call_expression(&mut self, call: &CallExpression, span: Span) -> bool {
+        let callee = match &call.func.kind {
+            ExpressionKind::Variable(path) => path.segments.last().map(|segment| &segment.ident),
+            ExpressionKind::TypePath(type_path) => Some(&type_path.item),
+            ExpressionKind::AsTraitPath(as_trait_path) => Some(&as_trait_path.impl_item),
+            _ => None,
+        };
+
+        if let Some(callee) = callee {
+            let call_site = CallSite { span, kind: CallKind::Function };
+            self.calls.push((callee.0.contents.clone(), call_site));
+        }
+        true
+    }
+
+    fn visit_method_call_expression(&mut self, call: &MethodCallExpression, span: Span) -> bool {
+        let call_site = CallSite { span, kind: CallKind::Method };
+        self.calls.push((call.method_name.0.contents.clone(), call_site));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::{find_call_sites, find_call_sites_with_kind, CallKind};
+
+    #[test]
+    fn finds_function_and_method_calls() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let a = helper(x);
+            let b = x.helper();
+            let c = foo::helper(helper(1));
+            let d = Field::helper(x) + not_helper(x);
+            a + b + c + d
+        }
+
+        fn helper(x: Field) -> Field { x }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let call_sites = find_call_sites_with_kind(&module, "helper");
+        let kinds: Vec<_> = call_sites.iter().map(|call_site| call_site.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                CallKind::Function,
+                CallKind::Method,
+                CallKind::Function,
+                CallKind::Function,
+                CallKind::Function,
+            ]
+        );
+
+        let starts: Vec<_> = call_sites.iter().map(|call_site| call_site.span.start()).collect();
+        let expected_starts = vec![
+            src.find("helper(x)").unwrap(),
+            src.find("x.helper()").unwrap(),
+            src.find("foo::helper").unwrap(),
+            src.find("helper(1)").unwrap(),
+            src.find("Field::helper").unwrap(),
+        ];
+        assert_eq!(
+            starts,
+            expected_starts.into_iter().map(|start| start as u32).collect::<Vec<_>>()
+        );
+
+        assert!(find_call_sites(&module, "main").is_empty());
+    }
+}
@@ -0,0 +1,99 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{ForLoopStatement, IfExpression, NoirFunction, Visitor},
+    ParsedModule,
+};
+
+/// Returns the cyclomatic complexity of `function`, counted on its body as written:
+/// - every function starts at 1,
+/// - each `if` adds 1, so an `else if` chain adds 1 per condition while a plain `else` adds
+///   nothing,
+/// - each `for` loop adds 1.
+///
+/// Branches inside lambdas and `comptime` or `unsafe` blocks in the body are included. Noir has no
+/// short-circuiting operators (`&` and `|` always evaluate both operands) nor `match` expressions,
+/// so no other construct is counted.
+pub fn complexity(function: &NoirFunction) -> u32 {
+    let mut counter = ComplexityCounter { complexity: 1 };
+    function.def.body.accept(None, &mut counter);
+    counter.complexity
+}
+
+/// Returns the highest [complexity] of the functions in `module`, including methods and functions
+/// declared in inline submodules, or `None` if it has no functions.
+pub fn max_complexity(module: &ParsedModule) -> Option<u32> {
+    let mut finder = MaxComplexityFinder { max: None };
+    module.accept(&mut finder);
+    finder.max
+}
+
+struct ComplexityCounter {
+    complexity: u32,
+}
+
+impl Visitor for ComplexityCounter {
+    fn visit_if_expression(&mut self, _: &IfExpression, _: Span) -> bool {
+        self.complexity += 1;
+        true
+    }
+
+    fn visit_for_loop_statement(&mut self, _: &ForLoopStatement) -> bool {
+        self.complexity += 1;
+        true
+    }
+}
+
+struct MaxComplexityFinder {
+    max: Option<u32>,
+}
+
+impl Visitor for MaxComplexityFinder {
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        let complexity = complexity(function);
+        self.max = Some(self.max.map_or(complexity, |max| max.max(complexity)));
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{parse_program, ItemKind};
+
+    use super::{complexity, max_complexity};
+
+    #[test]
+    fn counts_branching_points() {
+        let src = "
+        fn straight(x: Field) -> Field { x + 1 }
+
+        fn branching(x: u32) -> u32 {
+            let mut sum = 0;
+            for i in 0..x {
+                if i == 0 {
+                    sum += 1;
+                } else if i == 1 {
+                    sum += 2;
+                } else {
+                    sum += 3;
+                }
+            }
+            let f = |y: u32| if y > 1 { y } else { 0 };
+            f(sum) & 1
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let complexities: Vec<_> = module
+            .items
+            .iter()
+            .map(|item| match &item.kind {
+                ItemKind::Function(function) => complexity(function),
+                _ => panic!("Expected a function"),
+            })
+            .collect();
+        assert_eq!(complexities, vec![1, 5]);
+        assert_eq!(max_complexity(&module), Some(5));
+    }
+}
@@ -0,0 +1,121 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{ConstrainKind, ConstrainStatement, ExpressionKind, Literal, Visitor},
+    ParsedModule,
+};
+
+/// An `assert`, `assert_eq` or `constrain` statement, found by [collect_constraints].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintInfo {
+    pub kind: ConstrainKind,
+    /// The span of the whole statement.
+    pub span: Span,
+    /// The span of the asserted condition, or of both compared expressions for `assert_eq`.
+    pub condition_span: Span,
+    /// The span of the message, if one was given.
+    pub message_span: Option<Span>,
+    /// The text of the message, if it's a string literal.
+    pub message: Option<String>,
+}
+
+/// Returns every constraint in `module` in the order they appear in the source, including
+/// those nested in blocks, loops, `if`s, lambdas and `comptime` or `unsafe` blocks.
+pub fn collect_constraints(module: &ParsedModule) -> Vec<ConstraintInfo> {
+    let mut collector = ConstraintCollector::default();
+    module.accept(&mut collector);
+
+    let mut constraints = collector.constraints;
+    constraints.sort_by_key(|constraint| constraint.span.start());
+    constraints
+}
+
+#[derive(Default)]
+struct ConstraintCollector {
+    constraints: Vec<ConstraintInfo>,
+}
+
+impl Visitor for ConstraintCollector {
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainStatement) -> bool {
+        let condition_count = constrain.kind.required_arguments_count();
+        let (conditions, message) = if constrain.arguments.len() > condition_count {
+            let (conditions, rest) = constrain.arguments.split_at(condition_count);
+            (conditions, rest.first())
+        } else {
+            (constrain.arguments.as_slice(), None)
+        };
+
+        let condition_span = conditions
+            .iter()
+            .map(|condition| condition.span)
+            .reduce(Span::merge)
+            .unwrap_or(constrain.span);
+        let message_text = message.and_then(|message| match &message.kind {
+            ExpressionKind::Literal(Literal::Str(text) | Literal::RawStr(text, _)) => {
+                Some(text.clone())
+            }
+            _ => None,
+        });
+
+        self.constraints.push(ConstraintInfo {
+            kind: constrain.kind,
+            span: constrain.span,
+            condition_span,
+            message_span: message.map(|message| message.span),
+            message: message_text,
+        });
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ast::ConstrainKind, parser::parse_program};
+
+    use super::collect_constraints;
+
+    #[test]
+    fn collects_nested_constraints() {
+        let src = "
+        fn main(x: Field, y: Field) {
+            assert(x != 0);
+            for i in 0..3 {
+                if x == y {
+                    assert_eq(x, y + i, \"not equal\");
+                }
+            }
+            let f = |z: Field| {
+                assert(z == x, f\"bad {z}\");
+            };
+            comptime {
+                assert(true, \"comptime\");
+            }
+            f(y);
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let constraints = collect_constraints(&module);
+        let kinds: Vec<_> = constraints.iter().map(|constraint| constraint.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ConstrainKind::Assert,
+                ConstrainKind::AssertEq,
+                ConstrainKind::Assert,
+                ConstrainKind::Assert,
+            ]
+        );
+
+        let messages: Vec<_> =
+            constraints.iter().map(|constraint| constraint.message.as_deref()).collect();
+        assert_eq!(messages, vec![None, Some("not equal"), None, Some("comptime")]);
+        assert!(constraints[0].message_span.is_none());
+        assert!(constraints[2].message_span.is_some());
+
+        let condition_span = constraints[1].condition_span;
+        let condition = &src[condition_span.start() as usize..condition_span.end() as usize];
+        assert_eq!(condition, "x, y + i");
+    }
+}
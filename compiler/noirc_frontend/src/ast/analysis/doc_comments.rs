@@ -0,0 +1,132 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::Visitor,
+    parser::{Item, ItemKind, ParsedSubModule},
+    ParsedModule,
+};
+
+/// The name of an item documented in a [ParsedModule], found by [collect_doc_comments].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemName {
+    /// The item name, qualified by the inline submodules it's declared in (e.g. `foo::Bar`).
+    pub path: String,
+    pub kind: DocumentedItemKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DocumentedItemKind {
+    Function,
+    Struct,
+    Trait,
+}
+
+/// Returns the doc comment of every function, struct and trait in `module` which has one,
+/// including items declared in inline submodules, in the order they appear in the source.
+///
+/// The lines of a doc comment (`///` or `/** ... */`) are joined with newlines, each kept as
+/// written after the comment marker (so usually with a leading space). Items without a doc
+/// comment aren't returned.
+pub fn collect_doc_comments(module: &ParsedModule) -> Vec<(ItemName, String)> {
+    let mut collector = DocCommentCollector::default();
+    module.accept(&mut collector);
+    collector.doc_comments
+}
+
+#[derive(Default)]
+struct DocCommentCollector {
+    doc_comments: Vec<(ItemName, String)>,
+    module_path: Vec<String>,
+}
+
+impl Visitor for DocCommentCollector {
+    fn visit_item(&mut self, item: &Item) -> bool {
+        let (name, kind) = match &item.kind {
+            ItemKind::Function(function) => (function.name(), DocumentedItemKind::Function),
+            ItemKind::Struct(noir_struct) => {
+                (noir_struct.name.0.contents.as_str(), DocumentedItemKind::Struct)
+            }
+            ItemKind::Trait(noir_trait) => {
+                (noir_trait.name.0.contents.as_str(), DocumentedItemKind::Trait)
+            }
+            ItemKind::Submodules(_) => return true,
+            _ => return false,
+        };
+
+        if !item.doc_comments.is_empty() {
+            let mut path = self.module_path.clone();
+            path.push(name.to_string());
+            let item_name = ItemName { path: path.join("::"), kind };
+            self.doc_comments.push((item_name, item.doc_comments.join("\n")));
+        }
+        false
+    }
+
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, _: Span) -> bool {
+        self.module_path.push(submodule.name.to_string());
+        submodule.accept_children(self);
+        self.module_path.pop();
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::{collect_doc_comments, DocumentedItemKind};
+
+    #[test]
+    fn collects_doc_comments_with_qualified_names() {
+        let src = "
+        /// Entry point.
+        fn main() {}
+
+        fn undocumented() {}
+
+        /// A point.
+        /// Has two coordinates.
+        struct Point { x: Field, y: Field }
+
+        mod inner {
+            /// Something to implement.
+            trait Foo {}
+
+            struct Undocumented {}
+
+            mod nested {
+                /** Does nothing. */
+                fn nothing() {}
+            }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let doc_comments: Vec<_> = collect_doc_comments(&module)
+            .into_iter()
+            .map(|(name, doc_comment)| (name.path, name.kind, doc_comment))
+            .collect();
+        assert_eq!(
+            doc_comments,
+            vec![
+                ("main".to_string(), DocumentedItemKind::Function, " Entry point.".to_string()),
+                (
+                    "Point".to_string(),
+                    DocumentedItemKind::Struct,
+                    " A point.\n Has two coordinates.".to_string()
+                ),
+                (
+                    "inner::Foo".to_string(),
+                    DocumentedItemKind::Trait,
+                    " Something to implement.".to_string()
+                ),
+                (
+                    "inner::nested::nothing".to_string(),
+                    DocumentedItemKind::Function,
+                    " Does nothing. ".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use noirc_errors::Span;
+
+use crate::{
+    ast::{NoirStruct, Visitor},
+    ParsedModule,
+};
+
+/// Returns every field of a struct in `module` (including structs declared in inline
+/// submodules) which has the same name as an earlier field of that struct, as the struct's name,
+/// the field's name and the span of the field's name, in the order they appear in the source.
+///
+/// A field repeated `n` times is reported `n - 1` times, once for each repetition after the
+/// first.
+pub fn find_duplicate_fields(module: &ParsedModule) -> Vec<(String, String, Span)> {
+    let mut finder = DuplicateFieldFinder::default();
+    module.accept(&mut finder);
+    finder.duplicates
+}
+
+#[derive(Default)]
+struct DuplicateFieldFinder {
+    duplicates: Vec<(String, String, Span)>,
+}
+
+impl Visitor for DuplicateFieldFinder {
+    fn visit_noir_struct(&mut self, noir_struct: &NoirStruct, _: Span) -> bool {
+        let mut names = HashSet::new();
+        for field in &noir_struct.fields {
+            let name = &field.item.name;
+            if !names.insert(&name.0.contents) {
+                let struct_name = noir_struct.name.0.contents.clone();
+                self.duplicates.push((struct_name, name.0.contents.clone(), name.span()));
+            }
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::find_duplicate_fields;
+
+    #[test]
+    fn finds_repeated_fields() {
+        let src = "
+        struct Clean { x: Field, y: Field }
+
+        struct Repeated { x: Field, y: Field, x: u8, z: Field, x: bool, y: Field }
+
+        mod inner {
+            struct Repeated { a: Field, a: Field }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let duplicates = find_duplicate_fields(&module);
+        let names: Vec<_> = duplicates
+            .iter()
+            .map(|(struct_name, field_name, _)| (struct_name.as_str(), field_name.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("Repeated", "x"), ("Repeated", "x"), ("Repeated", "y"), ("Repeated", "a")]
+        );
+
+        let (_, _, span) = &duplicates[0];
+        assert_eq!(span.start() as usize, src.find("x: u8").unwrap());
+    }
+}
@@ -0,0 +1,115 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        BlockExpression, ExpressionKind, FunctionKind, FunctionReturnType, Ident, Literal,
+        NoirFunction, StatementKind, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+        Visitor,
+    },
+    ParsedModule,
+};
+
+/// Returns the name and the span of the name of every function in `module` (including methods
+/// and default trait methods) whose body does nothing, in the order they appear in the source.
+///
+/// A body does nothing if it has no statements, or if its only statements are unit values (as in
+/// `();`) and blocks which do nothing themselves. Functions marked as `#[builtin]`, `#[foreign]`
+/// or `#[oracle]` are implemented outside of Noir, so their empty bodies are never reported, nor
+/// are trait methods without a default body.
+pub fn find_empty_bodies(module: &ParsedModule) -> Vec<(String, Span)> {
+    let mut finder = EmptyBodyFinder::default();
+    module.accept(&mut finder);
+    finder.empty_bodies
+}
+
+#[derive(Default)]
+struct EmptyBodyFinder {
+    empty_bodies: Vec<(String, Span)>,
+}
+
+impl EmptyBodyFinder {
+    fn check(&mut self, name: &Ident, body: &BlockExpression) {
+        if is_empty(body) {
+            self.empty_bodies.push((name.0.contents.clone(), name.span()));
+        }
+    }
+}
+
+impl Visitor for EmptyBodyFinder {
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        if function.kind == FunctionKind::Normal {
+            self.check(function.name_ident(), &function.def.body);
+        }
+        false
+    }
+
+    fn visit_trait_item_function(
+        &mut self,
+        name: &Ident,
+        _generics: &UnresolvedGenerics,
+        _parameters: &[(Ident, UnresolvedType)],
+        _return_type: &FunctionReturnType,
+        _where_clause: &[UnresolvedTraitConstraint],
+        body: &Option<BlockExpression>,
+    ) -> bool {
+        if let Some(body) = body {
+            self.check(name, body);
+        }
+        false
+    }
+}
+
+fn is_empty(block: &BlockExpression) -> bool {
+    block.statements.iter().all(|statement| match &statement.kind {
+        StatementKind::Expression(expression) | StatementKind::Semi(expression) => {
+            match &expression.kind {
+                ExpressionKind::Literal(Literal::Unit) => true,
+                ExpressionKind::Block(block) => is_empty(block),
+                _ => false,
+            }
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::find_empty_bodies;
+
+    #[test]
+    fn finds_only_empty_bodies() {
+        let src = "
+        fn empty() {}
+        fn no_ops() { (); {} { (); }; }
+        fn not_empty(x: Field) { assert(x != 0); }
+        fn nested_not_empty(x: Field) { { assert(x != 0); } }
+
+        #[builtin(foo)]
+        fn builtin() {}
+
+        #[oracle(bar)]
+        unconstrained fn oracle() {}
+
+        trait Foo {
+            fn required();
+            fn default_empty() {}
+            fn default_not_empty() -> Field { 1 }
+        }
+
+        impl Foo for Field {
+            fn required() {}
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let empty_bodies = find_empty_bodies(&module);
+        let names: Vec<_> = empty_bodies.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["empty", "no_ops", "default_empty", "required"]);
+
+        let (_, span) = &empty_bodies[0];
+        assert_eq!(&src[span.start() as usize..span.end() as usize], "empty");
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::{BTreeSet, HashSet};
+
+use noirc_errors::Span;
+
+use crate::{
+    ast::{ItemVisibility, Path, PathKind, UseTree, Visitor},
+    parser::{ItemKind, ParsedSubModule},
+    ParsedModule,
+};
+
+/// Returns the name of every external crate referred to by `module`, including its inline
+/// submodules.
+///
+/// A `dep::foo::...` path always refers to the crate `foo`, wherever it appears. A plain `use`
+/// path such as `use foo::bar;` refers to the crate `foo` unless `foo` is an item declared in the
+/// importing module (including a submodule or a `mod foo;` declaration) or a name imported by
+/// another `use` of that module, mirroring how imports are resolved. Paths starting with
+/// `crate`, `super` or `self` never refer to an external crate. Other plain paths, such as the
+/// `foo` in `foo::bar()`, aren't counted as they might refer to items brought into scope by the
+/// prelude.
+pub fn collect_external_crate_refs(module: &ParsedModule) -> BTreeSet<String> {
+    let mut collector = ExternalCrateCollector {
+        local_names: local_names(module),
+        external_crates: BTreeSet::new(),
+    };
+    module.accept(&mut collector);
+    collector.external_crates
+}
+
+struct ExternalCrateCollector {
+    /// The names declared or imported in the module currently being visited.
+    local_names: HashSet<String>,
+    external_crates: BTreeSet<String>,
+}
+
+impl Visitor for ExternalCrateCollector {
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, _: Span) -> bool {
+        let local_names = local_names(&submodule.contents);
+        let parent_local_names = std::mem::replace(&mut self.local_names, local_names);
+        submodule.accept_children(self);
+        self.local_names = parent_local_names;
+        false
+    }
+
+    fn visit_import(&mut self, use_tree: &UseTree, _: Span, visibility: ItemVisibility) -> bool {
+        for import in use_tree.clone().desugar(None, visibility) {
+            let Some(first_segment) = import.path.segments.first() else {
+                continue;
+            };
+            let name = &first_segment.ident.0.contents;
+
+            let is_external = match import.path.kind {
+                PathKind::Dep => true,
+                PathKind::Plain => name != "self" && !self.local_names.contains(name),
+                PathKind::Crate | PathKind::Super => false,
+            };
+            if is_external {
+                self.external_crates.insert(name.clone());
+            }
+        }
+        false
+    }
+
+    fn visit_path(&mut self, path: &Path) {
+        if path.kind == PathKind::Dep {
+            if let Some(name) = path.first_name() {
+                self.external_crates.insert(name.to_string());
+            }
+        }
+    }
+}
+
+/// Returns the names of the items declared in `module`, along with the names its imports bring
+/// into scope. An import of a single segment without an alias, as in `use foo;`, only names an
+/// external crate so it isn't counted.
+fn local_names(module: &ParsedModule) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for item in &module.items {
+        let name = match &item.kind {
+            ItemKind::Function(function) => function.name_ident(),
+            ItemKind::Struct(noir_struct) => &noir_struct.name,
+            ItemKind::Trait(noir_trait) => &noir_trait.name,
+            ItemKind::TypeAlias(type_alias) => &type_alias.name,
+            ItemKind::ModuleDecl(module_declaration) => &module_declaration.ident,
+            ItemKind::Submodules(submodule) => &submodule.name,
+            ItemKind::Global(let_statement, _) => let_statement.pattern.name_ident(),
+            ItemKind::Import(use_tree, visibility) => {
+                for import in use_tree.clone().desugar(None, *visibility) {
+                    let is_crate_import = import.path.kind == PathKind::Plain
+                        && import.path.segments.len() == 1
+                        && import.alias.is_none();
+                    if import.path.segments.is_empty() || is_crate_import {
+                        continue;
+                    }
+
+                    let name = import.alias.unwrap_or_else(|| import.path.last_ident());
+                    names.insert(name.0.contents);
+                }
+                continue;
+            }
+            ItemKind::TraitImpl(_) | ItemKind::Impl(_) | ItemKind::InnerAttribute(_) => continue,
+        };
+        names.insert(name.0.contents.clone());
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::collect_external_crate_refs;
+
+    #[test]
+    fn collects_external_crates_from_imports_and_dep_paths() {
+        let src = "
+        use std::hash::Hash;
+        use dep::ec::{consts, tecurve::affine};
+        use crate::local::Thing;
+        use local::Other;
+        use Hash as H;
+        use aztec;
+        use sha256;
+        use poseidon::bn254::hash_2;
+
+        mod local {
+            use super::Thing;
+            use bigint::BigNum;
+            use bigint::U256;
+
+            fn foo() -> Field {
+                dep::keccak::hash(dep::std::hash::bar(), local_fn(), U256::zero())
+            }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let crates: Vec<_> = collect_external_crate_refs(&module).into_iter().collect();
+        assert_eq!(crates, vec!["aztec", "bigint", "ec", "keccak", "poseidon", "sha256", "std"]);
+    }
+}
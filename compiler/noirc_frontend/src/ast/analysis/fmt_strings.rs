@@ -0,0 +1,73 @@
+use noirc_errors::Span;
+use regex::Regex;
+
+use crate::{
+    ast::{Expression, ExpressionKind, Literal, Visitor},
+    ParsedModule,
+};
+
+/// Returns the span of every format string in `module` along with the names interpolated in it,
+/// in the order they appear in the source.
+///
+/// Placeholders are found the same way the elaborator finds them: a placeholder is a name made of
+/// ASCII letters, digits and underscores directly between braces, as in `{x}`. Format strings
+/// have no escape for braces, so the `x` in `{{x}}` is interpolated too, while `{ x }` and `{}`
+/// are printed as is. A name is returned once per placeholder, even if it's repeated. Format
+/// strings in quoted code aren't returned.
+pub fn collect_fmt_interpolations(module: &ParsedModule) -> Vec<(Span, Vec<String>)> {
+    let placeholder = fmt_str_placeholder();
+    let mut collector = InterpolationCollector { placeholder, interpolations: Vec::new() };
+    module.accept(&mut collector);
+    collector.interpolations
+}
+
+/// Returns the pattern the elaborator uses to find placeholders in format strings, whose first
+/// capture group is the interpolated name.
+pub(super) fn fmt_str_placeholder() -> Regex {
+    Regex::new(r"\{([a-zA-Z0-9_]+)\}")
+        .expect("ICE: an invalid regex pattern was used for finding format string placeholders")
+}
+
+struct InterpolationCollector {
+    placeholder: Regex,
+    interpolations: Vec<(Span, Vec<String>)>,
+}
+
+impl Visitor for InterpolationCollector {
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        if let ExpressionKind::Literal(Literal::FmtStr(string)) = &expression.kind {
+            let names = self.placeholder.captures_iter(string);
+            let names = names.map(|captures| captures[1].to_string()).collect();
+            self.interpolations.push((expression.span, names));
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::collect_fmt_interpolations;
+
+    #[test]
+    fn collects_interpolated_names() {
+        let src = r#"
+        fn main(x: Field, secret_key: Field) {
+            let y = x + 1;
+            assert(x != 0, f"x was {x}, y was {y} and x again {x}");
+            println(f"no placeholders { x } {} here");
+            foo(f"{{secret_key}} {_1}");
+        }
+        "#;
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let interpolations = collect_fmt_interpolations(&module);
+        let names: Vec<_> = interpolations.iter().map(|(_, names)| names.clone()).collect();
+        assert_eq!(names, vec![vec!["x", "y", "x"], vec![], vec!["secret_key", "_1"]]);
+
+        let (span, _) = &interpolations[0];
+        assert!(src[span.start() as usize..span.end() as usize].contains("x was {x}"));
+    }
+}
@@ -0,0 +1,617 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    fmt::Display,
+    hash::{Hash, Hasher},
+    mem::discriminant,
+};
+
+use acvm::FieldElement;
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        AttributeTarget, BlockExpression, CallExpression, CastExpression, ConstrainStatement,
+        ConstructorExpression, Expression, ForLoopStatement, ForRange, GenericTypeArgs, Ident,
+        IfExpression, InfixExpression, IntegerBitSize, LValue, Lambda, LetStatement, Literal,
+        MemberAccessExpression, MethodCallExpression, ModuleDeclaration, NoirFunction, NoirStruct,
+        NoirTrait, NoirTraitImpl, NoirTypeAlias, Path, Pattern, PrefixExpression, Signedness,
+        Statement, TraitImplItemKind, TraitItem, TypeImpl, TypePath, UnresolvedGenerics,
+        UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression, UseTree, UseTreeKind,
+        Visitor,
+    },
+    parser::{Item, ItemKind, ParsedSubModule},
+    token::{SecondaryAttribute, Tokens},
+    ParsedModule, QuotedType,
+};
+
+/// Returns a hash of `module` which only depends on its structure and contents, not on its spans.
+///
+/// Two modules which only differ in the location of their nodes (for instance the same code with
+/// different whitespace, or the same macro output generated at two call sites) hash equally.
+/// Doc comments and attributes are part of the hash. The hash is deterministic for a given build of
+/// the compiler, but isn't guaranteed to be stable across compiler versions.
+pub fn hash_module(module: &ParsedModule) -> u64 {
+    let mut hasher = ModuleHasher { hasher: DefaultHasher::new() };
+    module.accept(&mut hasher);
+    hasher.hasher.finish()
+}
+
+/// Returns a hash of the signature of `function`: its name, generics, parameter types and return
+/// type, hashed like [hash_module] hashes them.
+///
+/// The body, the names of the parameters and every visibility, attribute and modifier are left
+/// out, so two functions with the same name and types fingerprint equally whatever they do.
+pub fn signature_fingerprint(function: &NoirFunction) -> u64 {
+    let mut hasher = ModuleHasher { hasher: DefaultHasher::new() };
+    let def = &function.def;
+    hasher.write(&def.name);
+    hasher.write_generics(&def.generics);
+    hasher.write(def.parameters.len());
+    for param in &def.parameters {
+        param.typ.accept(&mut hasher);
+    }
+    def.return_type.accept(&mut hasher);
+    hasher.hasher.finish()
+}
+
+/// Feeds the kind of every node, together with any data it carries besides its children and
+/// spans, into a hasher in a pre-order traversal.
+///
+/// The lengths of lists of child nodes are hashed too so that, for example, `f(a, b)` and
+/// `f(a)` followed by `b` can't produce the same sequence of values.
+struct ModuleHasher {
+    hasher: DefaultHasher,
+}
+
+impl ModuleHasher {
+    fn write(&mut self, value: impl Hash) {
+        value.hash(&mut self.hasher);
+    }
+
+    /// Hashes the textual form of nodes which don't implement `Hash`, or whose `Hash`
+    /// implementation includes their span.
+    fn write_display(&mut self, value: impl Display) {
+        self.write(value.to_string());
+    }
+
+    fn write_generics(&mut self, generics: &UnresolvedGenerics) {
+        self.write(generics.len());
+        for generic in generics {
+            self.write_display(generic);
+        }
+    }
+}
+
+impl Visitor for ModuleHasher {
+    fn visit_parsed_module(&mut self, module: &ParsedModule) -> bool {
+        self.write(&module.inner_doc_comments);
+        self.write(module.items.len());
+        true
+    }
+
+    fn visit_item(&mut self, item: &Item) -> bool {
+        self.write(discriminant(&item.kind));
+        self.write(&item.doc_comments);
+        match &item.kind {
+            ItemKind::Import(_, visibility) | ItemKind::Global(_, visibility) => {
+                self.write(visibility);
+            }
+            _ => (),
+        }
+        true
+    }
+
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, _: Span) -> bool {
+        self.write(&submodule.name);
+        self.write(submodule.visibility);
+        self.write(submodule.is_contract);
+        true
+    }
+
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        let def = &function.def;
+        self.write(discriminant(&function.kind));
+        self.write(&def.name);
+        self.write(def.visibility);
+        self.write(def.is_unconstrained);
+        self.write(def.is_comptime);
+        self.write(def.attributes.function());
+        self.write(def.return_visibility);
+        self.write_generics(&def.generics);
+
+        self.write(def.parameters.len());
+        for param in &def.parameters {
+            self.write(param.visibility);
+            param.pattern.accept(self);
+        }
+        true
+    }
+
+    fn visit_noir_trait_impl(&mut self, trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        self.write_generics(&trait_impl.impl_generics);
+        self.write(trait_impl.is_synthetic);
+        trait_impl.trait_generics.accept(self);
+
+        self.write(trait_impl.where_clause.len());
+        for constraint in &trait_impl.where_clause {
+            constraint.accept(self);
+        }
+
+        self.write(trait_impl.items.len());
+        for item in &trait_impl.items {
+            self.write(&item.doc_comments);
+        }
+        true
+    }
+
+    fn visit_trait_impl_item_kind(&mut self, kind: &TraitImplItemKind, _: Span) -> bool {
+        self.write(discriminant(kind));
+        match kind {
+            TraitImplItemKind::Function(_) => (),
+            TraitImplItemKind::Constant(name, ..) | TraitImplItemKind::Type { name, .. } => {
+                self.write(name);
+            }
+        }
+        true
+    }
+
+    fn visit_type_impl(&mut self, type_impl: &TypeImpl, _: Span) -> bool {
+        self.write_generics(&type_impl.generics);
+
+        self.write(type_impl.where_clause.len());
+        for constraint in &type_impl.where_clause {
+            constraint.accept(self);
+        }
+
+        self.write(type_impl.methods.len());
+        for (method, _) in &type_impl.methods {
+            self.write(&method.doc_comments);
+        }
+        true
+    }
+
+    fn visit_noir_trait(&mut self, noir_trait: &NoirTrait, _: Span) -> bool {
+        self.write(&noir_trait.name);
+        self.write(noir_trait.visibility);
+        self.write(noir_trait.is_alias);
+        self.write_generics(&noir_trait.generics);
+        self.write(noir_trait.bounds.len());
+        self.write(noir_trait.where_clause.len());
+
+        self.write(noir_trait.items.len());
+        for item in &noir_trait.items {
+            self.write(&item.doc_comments);
+        }
+        true
+    }
+
+    fn visit_trait_item(&mut self, item: &TraitItem) -> bool {
+        self.write(discriminant(item));
+        match item {
+            TraitItem::Function {
+                is_unconstrained,
+                visibility,
+                is_comptime,
+                name,
+                generics,
+                parameters,
+                where_clause,
+                body,
+                return_type: _,
+            } => {
+                self.write(is_unconstrained);
+                self.write(visibility);
+                self.write(is_comptime);
+                self.write(name);
+                self.write_generics(generics);
+                self.write(parameters.iter().map(|(name, _)| name).collect::<Vec<_>>());
+                self.write(where_clause.len());
+                self.write(body.is_some());
+            }
+            TraitItem::Constant { name, default_value, typ: _ } => {
+                self.write(name);
+                self.write(default_value.is_some());
+            }
+            TraitItem::Type { name } => self.write(name),
+        }
+        true
+    }
+
+    fn visit_use_tree(&mut self, use_tree: &UseTree) -> bool {
+        self.write(discriminant(&use_tree.kind));
+        self.write_display(&use_tree.prefix);
+        match &use_tree.kind {
+            UseTreeKind::Path(name, alias) => {
+                self.write(name);
+                self.write(alias);
+            }
+            UseTreeKind::List(use_trees) => self.write(use_trees.len()),
+        }
+        true
+    }
+
+    fn visit_noir_struct(&mut self, noir_struct: &NoirStruct, _: Span) -> bool {
+        self.write(&noir_struct.name);
+        self.write(noir_struct.visibility);
+        self.write_generics(&noir_struct.generics);
+
+        self.write(noir_struct.fields.len());
+        for field in &noir_struct.fields {
+            self.write(&field.doc_comments);
+            self.write(field.item.visibility);
+            self.write(&field.item.name);
+        }
+        true
+    }
+
+    fn visit_noir_type_alias(&mut self, type_alias: &NoirTypeAlias, _: Span) -> bool {
+        self.write(&type_alias.name);
+        self.write(type_alias.visibility);
+        self.write_generics(&type_alias.generics);
+        true
+    }
+
+    fn visit_module_declaration(&mut self, module_declaration: &ModuleDeclaration, _: Span) {
+        self.write(&module_declaration.ident);
+        self.write(module_declaration.visibility);
+    }
+
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        self.write(discriminant(&expression.kind));
+        true
+    }
+
+    fn visit_literal(&mut self, literal: &Literal, _: Span) -> bool {
+        self.write(discriminant(literal));
+        true
+    }
+
+    fn visit_literal_bool(&mut self, value: bool) {
+        self.write(value);
+    }
+
+    fn visit_literal_integer(&mut self, value: FieldElement, negative: bool) {
+        self.write(value);
+        self.write(negative);
+    }
+
+    fn visit_literal_str(&mut self, value: &str) {
+        self.write(value);
+    }
+
+    fn visit_literal_raw_str(&mut self, value: &str, hashes: u8) {
+        self.write(value);
+        self.write(hashes);
+    }
+
+    fn visit_literal_fmt_str(&mut self, value: &str) {
+        self.write(value);
+    }
+
+    fn visit_array_literal_standard(&mut self, elements: &[Expression]) -> bool {
+        self.write(elements.len());
+        true
+    }
+
+    fn visit_block_expression(&mut self, block: &BlockExpression, _: Option<Span>) -> bool {
+        self.write(block.statements.len());
+        true
+    }
+
+    fn visit_prefix_expression(&mut self, prefix: &PrefixExpression, _: Span) -> bool {
+        self.write(prefix.operator);
+        true
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression, _: Span) -> bool {
+        self.write(call.is_macro_call);
+        self.write(call.arguments.len());
+        true
+    }
+
+    fn visit_method_call_expression(&mut self, call: &MethodCallExpression, _: Span) -> bool {
+        self.write(&call.method_name);
+        self.write(call.is_macro_call);
+        self.write(call.arguments.len());
+
+        self.write(call.generics.as_ref().map(Vec::len));
+        for typ in call.generics.iter().flatten() {
+            typ.accept(self);
+        }
+        true
+    }
+
+    fn visit_constructor_expression(
+        &mut self,
+        constructor: &ConstructorExpression,
+        _: Span,
+    ) -> bool {
+        self.write(constructor.fields.iter().map(|(name, _)| name).collect::<Vec<_>>());
+        true
+    }
+
+    fn visit_member_access_expression(
+        &mut self,
+        member_access: &MemberAccessExpression,
+        _: Span,
+    ) -> bool {
+        self.write(&member_access.rhs);
+        true
+    }
+
+    fn visit_cast_expression(&mut self, cast: &CastExpression, _: Span) -> bool {
+        cast.r#type.accept(self);
+        true
+    }
+
+    fn visit_infix_expression(&mut self, infix: &InfixExpression, _: Span) -> bool {
+        self.write(infix.operator.contents);
+        true
+    }
+
+    fn visit_if_expression(&mut self, if_expression: &IfExpression, _: Span) -> bool {
+        self.write(if_expression.alternative.is_some());
+        true
+    }
+
+    fn visit_tuple(&mut self, elements: &[Expression], _: Span) -> bool {
+        self.write(elements.len());
+        true
+    }
+
+    fn visit_quote(&mut self, tokens: &Tokens) {
+        self.write(tokens.0.len());
+        for token in &tokens.0 {
+            self.write_display(token.token());
+        }
+    }
+
+    fn visit_lambda(&mut self, lambda: &Lambda, _: Span) -> bool {
+        self.write(lambda.parameters.len());
+        for (pattern, _) in &lambda.parameters {
+            pattern.accept(self);
+        }
+        lambda.return_type.accept(self);
+        true
+    }
+
+    fn visit_type_path(&mut self, type_path: &TypePath, _: Span) -> bool {
+        self.write(&type_path.item);
+        self.write(type_path.turbofish.is_some());
+        true
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) -> bool {
+        self.write(discriminant(&statement.kind));
+        true
+    }
+
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        self.write(let_statement.comptime);
+        true
+    }
+
+    fn visit_constrain_statement(&mut self, constrain: &ConstrainStatement) -> bool {
+        self.write_display(constrain.kind);
+        self.write(constrain.arguments.len());
+        true
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        self.write(&for_loop.identifier);
+        true
+    }
+
+    fn visit_for_range(&mut self, range: &ForRange) -> bool {
+        self.write(discriminant(range));
+        if let ForRange::Range(bounds) = range {
+            self.write(bounds.inclusive);
+        }
+        true
+    }
+
+    fn visit_lvalue(&mut self, lvalue: &LValue) -> bool {
+        self.write(discriminant(lvalue));
+        true
+    }
+
+    fn visit_lvalue_ident(&mut self, ident: &Ident) {
+        self.write(ident);
+    }
+
+    fn visit_lvalue_member_access(
+        &mut self,
+        _object: &LValue,
+        field_name: &Ident,
+        _span: Span,
+    ) -> bool {
+        self.write(field_name);
+        true
+    }
+
+    fn visit_unresolved_type(&mut self, typ: &UnresolvedType) -> bool {
+        self.write(discriminant(&typ.typ));
+        match &typ.typ {
+            UnresolvedTypeData::Named(_, _, is_synthesized) => self.write(is_synthesized),
+            UnresolvedTypeData::Function(args, ..) => self.write(args.len()),
+            UnresolvedTypeData::Tuple(types) => self.write(types.len()),
+            _ => (),
+        }
+        true
+    }
+
+    fn visit_array_type(
+        &mut self,
+        length: &UnresolvedTypeExpression,
+        _: &UnresolvedType,
+        _: Span,
+    ) -> bool {
+        self.write_display(length);
+        true
+    }
+
+    fn visit_function_type(
+        &mut self,
+        _: &[UnresolvedType],
+        _: &UnresolvedType,
+        _: &UnresolvedType,
+        unconstrained: bool,
+        _: Span,
+    ) -> bool {
+        self.write(unconstrained);
+        true
+    }
+
+    fn visit_expression_type(&mut self, expression: &UnresolvedTypeExpression, _: Span) {
+        self.write_display(expression);
+    }
+
+    fn visit_format_string_type(
+        &mut self,
+        length: &UnresolvedTypeExpression,
+        _: &UnresolvedType,
+        _: Span,
+    ) -> bool {
+        self.write_display(length);
+        true
+    }
+
+    fn visit_string_type(&mut self, length: &UnresolvedTypeExpression, _: Span) {
+        self.write_display(length);
+    }
+
+    fn visit_quoted_type(&mut self, typ: &QuotedType, _: Span) {
+        self.write(typ);
+    }
+
+    fn visit_integer_type(&mut self, signedness: Signedness, bit_size: IntegerBitSize, _: Span) {
+        self.write(signedness);
+        self.write(bit_size);
+    }
+
+    fn visit_path(&mut self, path: &Path) {
+        self.write_display(path);
+    }
+
+    fn visit_generic_type_args(&mut self, generics: &GenericTypeArgs) -> bool {
+        self.write(generics.ordered_args.len());
+        self.write(generics.named_args.iter().map(|(name, _)| name).collect::<Vec<_>>());
+        true
+    }
+
+    fn visit_pattern(&mut self, pattern: &Pattern) -> bool {
+        self.write(discriminant(pattern));
+        match pattern {
+            Pattern::Tuple(patterns, _) => self.write(patterns.len()),
+            Pattern::Struct(_, fields, _) => {
+                self.write(fields.iter().map(|(name, _)| name).collect::<Vec<_>>());
+            }
+            _ => (),
+        }
+        true
+    }
+
+    fn visit_identifier_pattern(&mut self, ident: &Ident) {
+        self.write(ident);
+    }
+
+    fn visit_mutable_pattern(&mut self, _: &Pattern, _: Span, is_synthesized: bool) -> bool {
+        self.write(is_synthesized);
+        true
+    }
+
+    fn visit_secondary_attribute(
+        &mut self,
+        attribute: &SecondaryAttribute,
+        target: AttributeTarget,
+    ) -> bool {
+        self.write_display(attribute);
+        self.write(discriminant(&target));
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{parse_program, ItemKind};
+
+    use super::{hash_module, signature_fingerprint};
+
+    fn hash_source(src: &str) -> u64 {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        hash_module(&module)
+    }
+
+    #[test]
+    fn ignores_spans() {
+        let src = "
+        struct Foo { x: Field }
+        #[test]
+        fn main(x: Field, y: pub [u8; 3]) -> pub Field {
+            let foo = Foo { x };
+            let f = |z: u32| z + 1;
+            for i in 0..3 { assert(y[i] != 0, \"zero\"); }
+            foo.x + f(1) as Field
+        }
+        ";
+        let reformatted = "struct Foo{x:Field}
+            #[test] fn main(x:Field,y:pub [u8;3])->pub Field{let foo=Foo{x};
+            let f=|z:u32|z+1;for i in 0..3{assert(y[i]!=0,\"zero\");}foo.x+f(1) as Field}";
+
+        assert_eq!(hash_source(src), hash_source(reformatted));
+        assert_eq!(hash_source(&format!("\n\n{src}")), hash_source(src));
+    }
+
+    #[test]
+    fn distinguishes_different_programs() {
+        let base = hash_source("fn main(x: Field) -> pub Field { x + 1 }");
+        let variants = [
+            "fn main(x: Field) -> pub Field { x + 2 }",
+            "fn main(x: Field) -> pub Field { x - 1 }",
+            "fn main(y: Field) -> pub Field { y + 1 }",
+            "fn main(x: Field) -> Field { x + 1 }",
+            "fn main(x: u32) -> pub Field { x + 1 }",
+            "pub fn main(x: Field) -> pub Field { x + 1 }",
+            "fn main(x: Field) -> pub Field { (x + 1) }",
+            "#[test] fn main(x: Field) -> pub Field { x + 1 }",
+        ];
+        for variant in variants {
+            assert_ne!(hash_source(variant), base, "{variant}");
+        }
+    }
+
+    fn fingerprint_source(src: &str) -> u64 {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        let ItemKind::Function(function) = &module.items[0].kind else {
+            panic!("Expected a function");
+        };
+        signature_fingerprint(function)
+    }
+
+    #[test]
+    fn fingerprints_only_signatures() {
+        let base = fingerprint_source("fn foo<T>(x: [T; 2], y: Field) -> T { x[0] }");
+        let same_signature = [
+            "fn foo<T>(x: [T; 2], y: Field) -> T { let z = y; x[1] }",
+            "pub unconstrained fn foo<T>(a: [T; 2], b: pub Field) -> pub T { a[0] }",
+            "#[test]\nfn  foo<T>(x:[T;2],y:Field)->T{x[0]}",
+        ];
+        for variant in same_signature {
+            assert_eq!(fingerprint_source(variant), base, "{variant}");
+        }
+
+        let different_signature = [
+            "fn foo<T>(x: [T; 2], y: u32) -> T { x[0] }",
+            "fn foo<T>(x: [T; 3], y: Field) -> T { x[0] }",
+            "fn foo<T>(x: [T; 2]) -> T { x[0] }",
+            "fn foo<T>(x: [T; 2], y: Field) -> Field { x[0] }",
+            "fn foo<U>(x: [U; 2], y: Field) -> U { x[0] }",
+            "fn bar<T>(x: [T; 2], y: Field) -> T { x[0] }",
+        ];
+        for variant in different_signature {
+            assert_ne!(fingerprint_source(variant), base, "{variant}");
+        }
+    }
+}
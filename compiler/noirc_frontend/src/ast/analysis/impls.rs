@@ -0,0 +1,223 @@
+use iter_extended::vecmap;
+use noirc_errors::Span;
+use serde::Serialize;
+
+use crate::{
+    ast::{NoirTraitImpl, TraitImplItemKind, TypeImpl, UnresolvedTypeData, Visitor},
+    ParsedModule,
+};
+
+use super::{signatures::function_signature, FunctionSignature};
+
+/// An `impl` or trait impl block, with every type rendered as Noir source code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImplInfo {
+    pub object_type: String,
+    /// The implemented trait, including its generics (e.g. `From<u8>`), if this is a trait impl.
+    pub trait_name: Option<String>,
+    pub generics: Vec<String>,
+    /// One entry per simple constraint, e.g. `T: Eq`.
+    pub where_clause: Vec<String>,
+    /// The signatures of the methods declared in this block, named without any module path.
+    pub methods: Vec<FunctionSignature>,
+}
+
+/// A trait impl for a given type, found by [impls_for_type].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TraitImplInfo {
+    /// The implemented trait, including its generics (e.g. `From<u8>`).
+    pub trait_name: String,
+    /// The names of the methods declared in this impl, in declaration order.
+    pub methods: Vec<String>,
+}
+
+/// Returns every `impl` and trait impl block in `module`, including those declared in inline
+/// submodules, in the order they appear in the source.
+pub fn collect_impls(module: &ParsedModule) -> Vec<ImplInfo> {
+    let mut collector = ImplCollector::default();
+    module.accept(&mut collector);
+    collector.impls
+}
+
+/// Returns every trait impl in `module`, including those declared in inline submodules, whose
+/// object type is named `type_name`, in the order they appear in the source.
+///
+/// Types are matched on the last segment of their path, ignoring generics, so `Foo`,
+/// `super::Foo` and `Foo<Field>` are all named `Foo`. Impls which aren't trait impls aren't
+/// returned.
+pub fn impls_for_type(module: &ParsedModule, type_name: &str) -> Vec<TraitImplInfo> {
+    let mut finder = TraitImplFinder { type_name, impls: Vec::new() };
+    module.accept(&mut finder);
+    finder.impls
+}
+
+#[derive(Default)]
+struct ImplCollector {
+    impls: Vec<ImplInfo>,
+}
+
+impl Visitor for ImplCollector {
+    fn visit_type_impl(&mut self, type_impl: &TypeImpl, _: Span) -> bool {
+        let object_type = type_impl.object_type.to_string();
+        let methods = vecmap(&type_impl.methods, |(method, _)| {
+            function_signature(
+                &method.item,
+                method.item.name().to_string(),
+                Some(object_type.clone()),
+            )
+        });
+
+        self.impls.push(ImplInfo {
+            object_type,
+            trait_name: None,
+            generics: vecmap(&type_impl.generics, ToString::to_string),
+            where_clause: vecmap(&type_impl.where_clause, ToString::to_string),
+            methods,
+        });
+        false
+    }
+
+    fn visit_noir_trait_impl(&mut self, trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        let object_type = trait_impl.object_type.to_string();
+        let methods = trait_impl
+            .items
+            .iter()
+            .filter_map(|item| match &item.item.kind {
+                TraitImplItemKind::Function(function) => Some(function_signature(
+                    function,
+                    function.name().to_string(),
+                    Some(object_type.clone()),
+                )),
+                TraitImplItemKind::Constant(..) | TraitImplItemKind::Type { .. } => None,
+            })
+            .collect();
+
+        self.impls.push(ImplInfo {
+            object_type,
+            trait_name: Some(format!("{}{}", trait_impl.trait_name, trait_impl.trait_generics)),
+            generics: vecmap(&trait_impl.impl_generics, ToString::to_string),
+            where_clause: vecmap(&trait_impl.where_clause, ToString::to_string),
+            methods,
+        });
+        false
+    }
+}
+
+struct TraitImplFinder<'a> {
+    type_name: &'a str,
+    impls: Vec<TraitImplInfo>,
+}
+
+impl Visitor for TraitImplFinder<'_> {
+    fn visit_noir_trait_impl(&mut self, trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        let UnresolvedTypeData::Named(path, _, _) = &trait_impl.object_type.typ else {
+            return false;
+        };
+        let Some(last_segment) = path.segments.last() else {
+            return false;
+        };
+        if last_segment.ident.0.contents != self.type_name {
+            return false;
+        }
+
+        let methods = trait_impl
+            .items
+            .iter()
+            .filter_map(|item| match &item.item.kind {
+                TraitImplItemKind::Function(function) => Some(function.name().to_string()),
+                TraitImplItemKind::Constant(..) | TraitImplItemKind::Type { .. } => None,
+            })
+            .collect();
+
+        self.impls.push(TraitImplInfo {
+            trait_name: format!("{}{}", trait_impl.trait_name, trait_impl.trait_generics),
+            methods,
+        });
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::{collect_impls, impls_for_type, TraitImplInfo};
+
+    #[test]
+    fn collects_impls_and_trait_impls() {
+        let src = "
+        struct Foo<T> { x: T }
+
+        impl<T> Foo<T> where T: Eq {
+            fn new(x: T) -> Self { Foo { x } }
+            fn get(self) -> T { self.x }
+        }
+
+        mod inner {
+            impl From<u8> for super::Foo<Field> {
+                let N: u32 = 1;
+                fn from(x: u8) -> Self { super::Foo { x: x as Field } }
+            }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let impls = collect_impls(&module);
+        assert_eq!(impls.len(), 2);
+
+        assert_eq!(impls[0].object_type, "Foo<T>");
+        assert_eq!(impls[0].trait_name, None);
+        assert_eq!(impls[0].generics, vec!["T"]);
+        assert_eq!(impls[0].where_clause, vec!["T: Eq"]);
+        let method_names: Vec<_> = impls[0].methods.iter().map(|method| &method.name).collect();
+        assert_eq!(method_names, vec!["new", "get"]);
+        assert_eq!(impls[0].methods[1].return_type.as_deref(), Some("T"));
+
+        assert_eq!(impls[1].object_type, "super::Foo<Field>");
+        assert_eq!(impls[1].trait_name.as_deref(), Some("From<u8>"));
+        assert!(impls[1].generics.is_empty());
+        assert_eq!(impls[1].methods.len(), 1);
+        assert_eq!(impls[1].methods[0].self_type.as_deref(), Some("super::Foo<Field>"));
+    }
+
+    #[test]
+    fn finds_trait_impls_for_a_type() {
+        let src = "
+        struct Foo { x: Field }
+        struct Bar { x: Field }
+
+        impl Foo {
+            fn new(x: Field) -> Self { Foo { x } }
+        }
+
+        impl Eq for Foo {
+            fn eq(self, other: Self) -> bool { self.x == other.x }
+        }
+
+        impl Eq for Bar {
+            fn eq(self, other: Self) -> bool { self.x == other.x }
+        }
+
+        mod inner {
+            impl From<u8> for super::Foo {
+                type Output = Field;
+                fn from(x: u8) -> Self { super::Foo { x: x as Field } }
+            }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        assert_eq!(
+            impls_for_type(&module, "Foo"),
+            vec![
+                TraitImplInfo { trait_name: "Eq".to_string(), methods: vec!["eq".to_string()] },
+                TraitImplInfo {
+                    trait_name: "From<u8>".to_string(),
+                    methods: vec!["from".to_string()],
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+//! Read-only queries over a [ParsedModule][crate::ParsedModule].
+//!
+//! These are built on top of the AST [Visitor][crate::ast::Visitor] and are meant for tooling
+//! that needs to inspect code (for instance code produced by a macro) before it is elaborated.
+mod array_lengths;
+mod call_graph;
+mod call_sites;
+mod complexity;
+mod constraints;
+mod doc_comments;
+mod duplicate_fields;
+mod empty_bodies;
+mod external_crates;
+mod fmt_strings;
+mod hash;
+mod impls;
+mod module_decls;
+mod naming;
+mod recursion;
+mod scope_tracker;
+mod shadowing;
+mod signatures;
+mod struct_widths;
+mod type_aliases;
+mod unused_generics;
+mod unused_lets;
+
+pub use array_lengths::*;
+pub use call_graph::*;
+pub use call_sites::*;
+pub use complexity::*;
+pub use constraints::*;
+pub use doc_comments::*;
+pub use duplicate_fields::*;
+pub use empty_bodies::*;
+pub use external_crates::*;
+pub use fmt_strings::*;
+pub use hash::*;
+pub use impls::*;
+pub use module_decls::*;
+pub use naming::*;
+pub use recursion::*;
+pub use shadowing::*;
+pub use signatures::*;
+pub use struct_widths::*;
+pub use type_aliases::*;
+pub use unused_generics::*;
+pub use unused_lets::*;
@@ -0,0 +1,105 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{ModuleDeclaration, Visitor},
+    parser::ParsedSubModule,
+    ParsedModule,
+};
+
+/// A module declared in a [ParsedModule], found by [collect_module_decls].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleDeclInfo {
+    /// The module name, qualified by the inline submodules it's declared in (e.g. `foo::bar`).
+    pub path: String,
+    /// The span of the whole declaration, including the body of an inline submodule.
+    pub span: Span,
+    pub kind: ModuleDeclKind,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ModuleDeclKind {
+    /// A `mod foo;` declaration, whose contents are in another file.
+    External,
+    /// A `mod foo { ... }` or `contract foo { ... }` declaration.
+    Inline,
+}
+
+/// Returns every module declared in `module`, including those declared in inline submodules, in
+/// the order they appear in the source.
+pub fn collect_module_decls(module: &ParsedModule) -> Vec<ModuleDeclInfo> {
+    let mut collector = ModuleDeclCollector::default();
+    module.accept(&mut collector);
+    collector.module_decls
+}
+
+#[derive(Default)]
+struct ModuleDeclCollector {
+    module_decls: Vec<ModuleDeclInfo>,
+    module_path: Vec<String>,
+}
+
+impl ModuleDeclCollector {
+    fn push(&mut self, name: String, span: Span, kind: ModuleDeclKind) {
+        let mut path = self.module_path.clone();
+        path.push(name);
+        self.module_decls.push(ModuleDeclInfo { path: path.join("::"), span, kind });
+    }
+}
+
+impl Visitor for ModuleDeclCollector {
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, span: Span) -> bool {
+        self.push(submodule.name.to_string(), span, ModuleDeclKind::Inline);
+
+        self.module_path.push(submodule.name.to_string());
+        submodule.accept_children(self);
+        self.module_path.pop();
+        false
+    }
+
+    fn visit_module_declaration(&mut self, module_declaration: &ModuleDeclaration, span: Span) {
+        self.push(module_declaration.ident.to_string(), span, ModuleDeclKind::External);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::{collect_module_decls, ModuleDeclKind};
+
+    #[test]
+    fn collects_external_and_inline_modules() {
+        let src = "
+        mod foo;
+
+        mod bar {
+            pub mod baz;
+
+            mod qux {
+                mod quux;
+            }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let module_decls = collect_module_decls(&module);
+        let paths_and_kinds: Vec<_> = module_decls
+            .iter()
+            .map(|module_decl| (module_decl.path.as_str(), module_decl.kind))
+            .collect();
+        assert_eq!(
+            paths_and_kinds,
+            vec![
+                ("foo", ModuleDeclKind::External),
+                ("bar", ModuleDeclKind::Inline),
+                ("bar::baz", ModuleDeclKind::External),
+                ("bar::qux", ModuleDeclKind::Inline),
+                ("bar::qux::quux", ModuleDeclKind::External),
+            ]
+        );
+
+        let foo_span = module_decls[0].span;
+        assert_eq!(&src[foo_span.start() as usize..foo_span.end() as usize], "mod foo;");
+    }
+}
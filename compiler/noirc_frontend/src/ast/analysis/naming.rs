@@ -0,0 +1,94 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{Ident, NoirFunction, NoirStruct, NoirTrait, Visitor},
+    ParsedModule,
+};
+
+/// Returns the name and span of every name declared in `module` for which `rule` returns
+/// `false`, in the order they appear in the source.
+///
+/// The names checked are those of functions (including methods), structs, traits and globals,
+/// along with the variables bound by local `let` statements.
+pub fn check_naming(module: &ParsedModule, rule: impl Fn(&str) -> bool) -> Vec<(String, Span)> {
+    let mut checker = NamingChecker { rule, violations: Vec::new() };
+    module.accept(&mut checker);
+
+    let mut violations = checker.violations;
+    violations.sort_by_key(|(_, span)| span.start());
+    violations
+}
+
+struct NamingChecker<F> {
+    rule: F,
+    violations: Vec<(String, Span)>,
+}
+
+impl<F: Fn(&str) -> bool> NamingChecker<F> {
+    fn check(&mut self, ident: &Ident) {
+        if !(self.rule)(&ident.0.contents) {
+            self.violations.push((ident.0.contents.clone(), ident.span()));
+        }
+    }
+}
+
+impl<F: Fn(&str) -> bool> Visitor for NamingChecker<F> {
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        self.check(function.name_ident());
+        true
+    }
+
+    fn visit_noir_struct(&mut self, noir_struct: &NoirStruct, _: Span) -> bool {
+        self.check(&noir_struct.name);
+        true
+    }
+
+    fn visit_noir_trait(&mut self, noir_trait: &NoirTrait, _: Span) -> bool {
+        self.check(&noir_trait.name);
+        true
+    }
+
+    fn visit_identifier_pattern(&mut self, ident: &Ident) {
+        // Both globals and local `let` statements bind their names through a pattern
+        self.check(ident);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::check_naming;
+
+    #[test]
+    fn reports_only_violating_names() {
+        let src = "
+        global __generated_G: Field = 1;
+        global G: Field = 2;
+
+        struct __generated_Point { x: Field }
+        struct Point { x: Field }
+
+        trait __generated_Foo {}
+        trait Foo {}
+
+        fn __generated_main() {
+            let __generated_a = 1;
+            let (b, __generated_c) = (2, 3);
+        }
+
+        impl Point {
+            fn helper(self) {}
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let violations = check_naming(&module, |name| name.starts_with("__generated_"));
+        let names: Vec<_> = violations.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["G", "Point", "Foo", "b", "helper"]);
+
+        let (_, span) = &violations[0];
+        assert_eq!(&src[span.start() as usize..span.end() as usize], "G");
+    }
+}
@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+
+use petgraph::{algo::tarjan_scc, graph::DiGraph};
+
+use crate::ParsedModule;
+
+use super::build_call_graph;
+
+/// Returns every group of functions of `module` which can call themselves, directly or through
+/// each other: a self-recursive function forms a group on its own, while mutually recursive
+/// functions form a single group. Functions are listed in the order they're declared in, and so
+/// are the groups (by their first function).
+///
+/// This includes functions in impls, trait impls and submodules as well as trait methods with a
+/// default body. As names aren't resolved, calls are matched on the final segment of the callee's
+/// path or on the method name, so `foo(x)`, `bar::foo(x)` and `x.foo()` are all calls to every
+/// function named `foo`. This may report recursion between unrelated functions sharing a name,
+/// but never misses recursion through direct calls. Calls through function values (such as
+/// lambdas passed as arguments) aren't followed.
+pub fn find_recursion(module: &ParsedModule) -> Vec<Vec<String>> {
+    let call_graph = build_call_graph(module);
+
+    let mut graph = DiGraph::<&str, ()>::new();
+    let mut indices = HashMap::new();
+    for name in call_graph.functions() {
+        indices.insert(name.as_str(), graph.add_node(name.as_str()));
+    }
+    for name in call_graph.functions() {
+        for callee in call_graph.callees(name).into_iter().flatten() {
+            graph.update_edge(indices[name.as_str()], indices[callee.as_str()], ());
+        }
+    }
+
+    let mut cycles: Vec<_> = tarjan_scc(&graph)
+        .into_iter()
+        .filter(|scc| scc.len() > 1 || graph.contains_edge(scc[0], scc[0]))
+        .map(|mut scc| {
+            scc.sort();
+            scc
+        })
+        .collect();
+    cycles.sort();
+
+    cycles
+        .into_iter()
+        .map(|scc| scc.into_iter().map(|index| graph[index].to_string()).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::find_recursion;
+
+    #[test]
+    fn finds_direct_and_mutual_recursion() {
+        let src = "
+        fn main(x: u32) -> pub u32 {
+            factorial(x) + is_even(x) as u32 + helper(x)
+        }
+
+        fn factorial(x: u32) -> u32 {
+            if x == 0 { 1 } else { x * factorial(x - 1) }
+        }
+
+        fn is_even(x: u32) -> bool {
+            if x == 0 { true } else { is_odd(x - 1) }
+        }
+
+        fn helper(x: u32) -> u32 { x }
+
+        impl Foo {
+            fn is_odd(x: u32) -> bool {
+                if x == 0 { false } else { x.minus_one().is_even() }
+            }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        assert_eq!(
+            find_recursion(&module),
+            vec![vec!["factorial".to_string()], vec!["is_even".to_string(), "is_odd".to_string()]]
+        );
+    }
+
+    #[test]
+    fn finds_no_recursion_in_acyclic_module() {
+        let src = "
+        fn main(x: u32) -> pub u32 { foo(x) + bar(x) }
+        fn foo(x: u32) -> u32 { bar(x) }
+        fn bar(x: u32) -> u32 { x }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        assert!(find_recursion(&module).is_empty());
+    }
+}
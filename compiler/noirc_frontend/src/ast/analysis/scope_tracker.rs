@@ -0,0 +1,209 @@
+use noirc_errors::Span;
+use regex::Regex;
+
+use crate::{
+    ast::{
+        BlockExpression, ForLoopStatement, FunctionReturnType, Ident, Lambda, LetStatement,
+        NoirFunction, Path, Pattern, UnresolvedGenerics, UnresolvedTraitConstraint, UnresolvedType,
+        Visitor,
+    },
+    token::{Token, Tokens},
+};
+
+use super::fmt_strings::fmt_str_placeholder;
+
+/// A local variable, along with the data a [ScopeHandler] keeps for it.
+pub(crate) struct Binding<T> {
+    pub(crate) name: String,
+    pub(crate) span: Span,
+    pub(crate) data: T,
+}
+
+/// The callbacks of a [ScopeTracker], called as it visits the local variables of a module.
+pub(crate) trait ScopeHandler {
+    /// The data kept for each binding.
+    type Data;
+
+    /// Called on each variable bound by a `let` statement (where `is_let` is `true`), or by a
+    /// function, lambda or `for` loop parameter, before it's in scope. `scopes` holds the bindings
+    /// in scope at that point, innermost scope last.
+    fn declare(
+        &mut self,
+        ident: &Ident,
+        is_let: bool,
+        scopes: &[Vec<Binding<Self::Data>>],
+    ) -> Self::Data;
+
+    /// Called on the bindings of each scope once it ends, in the order they were declared in.
+    fn exit_scope(&mut self, _bindings: Vec<Binding<Self::Data>>) {}
+
+    /// Called on the binding each use of a name resolves to.
+    fn use_binding(&mut self, _binding: &mut Binding<Self::Data>) {}
+}
+
+/// A [Visitor] which keeps track of the local variables in scope, following Noir's scoping rules,
+/// and calls its [ScopeHandler] on each of them.
+///
+/// A new scope is entered for each function, lambda, block and `for` loop body. A variable is used
+/// when it's referenced, assigned to, interpolated in a format string (as in `f"{x}"`, found the
+/// same way the elaborator does) or named anywhere in a `quote` expression. A use resolves to the
+/// innermost, most recent binding with that name.
+pub(crate) struct ScopeTracker<H: ScopeHandler> {
+    scopes: Vec<Vec<Binding<H::Data>>>,
+    placeholder: Regex,
+    pub(crate) handler: H,
+}
+
+impl<H: ScopeHandler> ScopeTracker<H> {
+    pub(crate) fn new(handler: H) -> Self {
+        ScopeTracker { scopes: Vec::new(), placeholder: fmt_str_placeholder(), handler }
+    }
+
+    /// Visits the parameters and body of `function` in a scope of their own.
+    pub(crate) fn visit_function(&mut self, function: &NoirFunction) {
+        self.push_scope();
+        for param in &function.def.parameters {
+            self.declare_pattern(&param.pattern, false);
+        }
+        function.def.body.accept(None, self);
+        self.pop_scope();
+    }
+
+    fn push_scope(&mut self) {
+        self.scopes.push(Vec::new());
+    }
+
+    fn pop_scope(&mut self) {
+        let scope = self.scopes.pop().expect("Expected a scope to pop");
+        self.handler.exit_scope(scope);
+    }
+
+    fn declare(&mut self, ident: &Ident, is_let: bool) {
+        let data = self.handler.declare(ident, is_let, &self.scopes);
+        if let Some(scope) = self.scopes.last_mut() {
+            let name = ident.0.contents.clone();
+            scope.push(Binding { name, span: ident.span(), data });
+        }
+    }
+
+    fn declare_pattern(&mut self, pattern: &Pattern, is_let: bool) {
+        match pattern {
+            Pattern::Identifier(ident) => self.declare(ident, is_let),
+            Pattern::Mutable(pattern, _, _) => self.declare_pattern(pattern, is_let),
+            Pattern::Tuple(patterns, _) => {
+                for pattern in patterns {
+                    self.declare_pattern(pattern, is_let);
+                }
+            }
+            Pattern::Struct(_, fields, _) => {
+                for (_, pattern) in fields {
+                    self.declare_pattern(pattern, is_let);
+                }
+            }
+            Pattern::Interned(..) => (),
+        }
+    }
+
+    fn use_name(&mut self, name: &str) {
+        let mut bindings = self.scopes.iter_mut().rev().flat_map(|scope| scope.iter_mut().rev());
+        if let Some(binding) = bindings.find(|binding| binding.name == name) {
+            self.handler.use_binding(binding);
+        }
+    }
+}
+
+impl<H: ScopeHandler> Visitor for ScopeTracker<H> {
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        self.visit_function(function);
+        false
+    }
+
+    fn visit_trait_item_function(
+        &mut self,
+        _name: &Ident,
+        _generics: &UnresolvedGenerics,
+        parameters: &[(Ident, UnresolvedType)],
+        _return_type: &FunctionReturnType,
+        _where_clause: &[UnresolvedTraitConstraint],
+        body: &Option<BlockExpression>,
+    ) -> bool {
+        self.push_scope();
+        for (name, _) in parameters {
+            self.declare(name, false);
+        }
+        if let Some(body) = body {
+            body.accept(None, self);
+        }
+        self.pop_scope();
+        false
+    }
+
+    fn visit_global(&mut self, let_statement: &LetStatement, _: Span) -> bool {
+        let_statement.expression.accept(self);
+        false
+    }
+
+    fn visit_block_expression(&mut self, block: &BlockExpression, _: Option<Span>) -> bool {
+        self.push_scope();
+        block.accept_children(self);
+        self.pop_scope();
+        false
+    }
+
+    fn visit_let_statement(&mut self, let_statement: &LetStatement) -> bool {
+        // The bound variables are not in scope in their own initializer.
+        let_statement.expression.accept(self);
+        self.declare_pattern(&let_statement.pattern, true);
+        false
+    }
+
+    fn visit_lambda(&mut self, lambda: &Lambda, _: Span) -> bool {
+        self.push_scope();
+        for (pattern, _) in &lambda.parameters {
+            self.declare_pattern(pattern, false);
+        }
+        lambda.body.accept(self);
+        self.pop_scope();
+        false
+    }
+
+    fn visit_for_loop_statement(&mut self, for_loop: &ForLoopStatement) -> bool {
+        for_loop.range.accept(self);
+
+        self.push_scope();
+        self.declare(&for_loop.identifier, false);
+        for_loop.block.accept(self);
+        self.pop_scope();
+        false
+    }
+
+    fn visit_variable(&mut self, path: &Path, _: Span) -> bool {
+        if let Some(ident) = path.as_ident() {
+            self.use_name(&ident.0.contents);
+        }
+        true
+    }
+
+    fn visit_lvalue_ident(&mut self, ident: &Ident) {
+        self.use_name(&ident.0.contents);
+    }
+
+    fn visit_literal_fmt_str(&mut self, string: &str) {
+        let names: Vec<_> = self
+            .placeholder
+            .captures_iter(string)
+            .map(|captures| captures[1].to_string())
+            .collect();
+        for name in names {
+            self.use_name(&name);
+        }
+    }
+
+    fn visit_quote(&mut self, tokens: &Tokens) {
+        for token in &tokens.0 {
+            if let Token::Ident(name) = token.token() {
+                self.use_name(name);
+            }
+        }
+    }
+}
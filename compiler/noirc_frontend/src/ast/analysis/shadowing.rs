@@ -0,0 +1,123 @@
+use noirc_errors::Span;
+
+use crate::ast::{Ident, NoirFunction};
+
+use super::scope_tracker::{Binding, ScopeHandler, ScopeTracker};
+
+/// Returns every variable bound by a `let` statement in `function` which shadows another
+/// variable with the same name that is in scope at that point, as its name, the span of the
+/// shadowing binding and the span of the shadowed one, in the order they appear in the source.
+///
+/// The shadowed variable may have been bound by a function, lambda or `for` loop parameter, or by
+/// another `let` in the same or an enclosing block. Variables bound by a block which has ended
+/// are no longer in scope, so a `let` following the block never shadows them.
+pub fn find_shadowing(function: &NoirFunction) -> Vec<(String, Span, Span)> {
+    let mut tracker = ScopeTracker::new(ShadowingFinder::default());
+    tracker.visit_function(function);
+
+    let mut shadowing = tracker.handler.shadowing;
+    shadowing.sort_by_key(|(_, span, _)| span.start());
+    shadowing
+}
+
+#[derive(Default)]
+struct ShadowingFinder {
+    shadowing: Vec<(String, Span, Span)>,
+}
+
+impl ScopeHandler for ShadowingFinder {
+    type Data = ();
+
+    fn declare(&mut self, ident: &Ident, is_let: bool, scopes: &[Vec<Binding<()>>]) {
+        let name = &ident.0.contents;
+        if is_let {
+            let mut bindings = scopes.iter().rev().flat_map(|scope| scope.iter().rev());
+            if let Some(shadowed) = bindings.find(|binding| binding.name == *name) {
+                self.shadowing.push((name.clone(), ident.span(), shadowed.span));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::NoirFunction,
+        parser::{parse_program, ItemKind},
+    };
+
+    use super::find_shadowing;
+
+    fn parse_function(src: &str) -> NoirFunction {
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        match module.items.remove(0).kind {
+            ItemKind::Function(function) => function,
+            _ => panic!("Expected a function"),
+        }
+    }
+
+    /// Returns the name of each shadowing `let` in `src`, along with the start offsets of the
+    /// shadowing and shadowed bindings.
+    fn shadowing_offsets(src: &str) -> Vec<(String, usize, usize)> {
+        let shadowing = find_shadowing(&parse_function(src));
+        let offsets = shadowing.into_iter().map(|(name, span, shadowed_span)| {
+            (name, span.start() as usize, shadowed_span.start() as usize)
+        });
+        offsets.collect()
+    }
+
+    #[test]
+    fn finds_shadowing_lets() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let x = x + 1;
+            let (a, mut b) = (1, 2);
+            let b = b;
+            for i in 0..3 {
+                let i = i * 2;
+                let a = i;
+            }
+            let f = |y: Field| {
+                let y = y + a;
+                y
+            };
+            f(x + b)
+        }
+        ";
+        assert_eq!(
+            shadowing_offsets(src),
+            vec![
+                ("x".to_string(), src.find("x = x").unwrap(), src.find("x: Field").unwrap()),
+                ("b".to_string(), src.find("b = b").unwrap(), src.find("b) =").unwrap()),
+                ("i".to_string(), src.find("i = i").unwrap(), src.find("i in").unwrap()),
+                ("a".to_string(), src.find("a = i").unwrap(), src.find("a, mut").unwrap()),
+                ("y".to_string(), src.find("y = y").unwrap(), src.find("y: Field").unwrap()),
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_bindings_which_are_out_of_scope() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            {
+                let a = x;
+                assert(a != 0);
+            }
+            let a = { let b = 1; b };
+            for i in 0..3 {
+                let c = i;
+                assert(c != 0);
+            }
+            let f = |y: Field| y;
+            let i = 0;
+            let c = i;
+            let y = f(c);
+            a + y
+        }
+        ";
+        assert!(shadowing_offsets(src).is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+use iter_extended::vecmap;
+use noirc_errors::Span;
+use serde::Serialize;
+
+use crate::{
+    ast::{FunctionReturnType, NoirFunction, NoirTraitImpl, TypeImpl, Visitor},
+    parser::ParsedSubModule,
+    ParsedModule,
+};
+
+/// The signature of a function, with every type rendered as Noir source code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FunctionSignature {
+    /// The function name, qualified by the inline submodules it's declared in (e.g. `foo::bar`).
+    pub name: String,
+    /// The type this function is a method of, if it's declared in an `impl` or a trait impl.
+    pub self_type: Option<String>,
+    pub parameters: Vec<ParameterSignature>,
+    /// `None` if the function doesn't declare a return type.
+    pub return_type: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParameterSignature {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub typ: String,
+}
+
+/// Returns the signature of every function in `module`, including methods declared in `impl`s
+/// and trait impls, and functions declared in inline submodules.
+///
+/// Function bodies are never traversed.
+pub fn extract_signatures(module: &ParsedModule) -> Vec<FunctionSignature> {
+    let mut collector = SignatureCollector::default();
+    module.accept(&mut collector);
+    collector.signatures
+}
+
+#[derive(Default)]
+struct SignatureCollector {
+    signatures: Vec<FunctionSignature>,
+    module_path: Vec<String>,
+    self_type: Option<String>,
+}
+
+impl Visitor for SignatureCollector {
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, _: Span) -> bool {
+        self.module_path.push(submodule.name.to_string());
+        submodule.accept_children(self);
+        self.module_path.pop();
+        false
+    }
+
+    fn visit_type_impl(&mut self, type_impl: &TypeImpl, _: Span) -> bool {
+        self.self_type = Some(type_impl.object_type.to_string());
+        type_impl.accept_children(self);
+        self.self_type = None;
+        false
+    }
+
+    fn visit_noir_trait_impl(&mut self, trait_impl: &NoirTraitImpl, _: Span) -> bool {
+        self.self_type = Some(trait_impl.object_type.to_string());
+        trait_impl.accept_children(self);
+        self.self_type = None;
+        false
+    }
+
+    fn visit_noir_function(&mut self, function: &NoirFunction, _: Span) -> bool {
+        let mut name = self.module_path.clone();
+        name.push(function.name().to_string());
+
+        let signature = function_signature(function, name.join("::"), self.self_type.clone());
+        self.signatures.push(signature);
+        false
+    }
+}
+
+pub(super) fn function_signature(
+    function: &NoirFunction,
+    name: String,
+    self_type: Option<String>,
+) -> FunctionSignature {
+    let parameters = vecmap(&function.def.parameters, |param| ParameterSignature {
+        name: param.pattern.to_string(),
+        typ: param.typ.to_string(),
+    });
+
+    let return_type = match &function.def.return_type {
+        FunctionReturnType::Default(_) => None,
+        FunctionReturnType::Ty(typ) => Some(typ.to_string()),
+    };
+
+    FunctionSignature { name, self_type, parameters, return_type }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::{extract_signatures, FunctionSignature, ParameterSignature};
+
+    fn parameter(name: &str, typ: &str) -> ParameterSignature {
+        ParameterSignature { name: name.to_string(), typ: typ.to_string() }
+    }
+
+    #[test]
+    fn extracts_signatures_of_all_functions() {
+        let src = "
+        fn main(x: Field, y: pub [u8; 2]) -> pub Field { x }
+
+        struct Foo {}
+
+        impl Foo {
+            fn new() -> Self { Foo {} }
+        }
+
+        impl Default for Foo {
+            fn default() -> Self { Foo::new() }
+        }
+
+        mod inner {
+            fn helper(mut a: (bool, u32)) {}
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let signatures = extract_signatures(&module);
+        assert_eq!(
+            signatures,
+            vec![
+                FunctionSignature {
+                    name: "main".to_string(),
+                    self_type: None,
+                    parameters: vec![parameter("x", "Field"), parameter("y", "[u8; 2]")],
+                    return_type: Some("Field".to_string()),
+                },
+                FunctionSignature {
+                    name: "new".to_string(),
+                    self_type: Some("Foo".to_string()),
+                    parameters: vec![],
+                    return_type: Some("Self".to_string()),
+                },
+                FunctionSignature {
+                    name: "default".to_string(),
+                    self_type: Some("Foo".to_string()),
+                    parameters: vec![],
+                    return_type: Some("Self".to_string()),
+                },
+                FunctionSignature {
+                    name: "inner::helper".to_string(),
+                    self_type: None,
+                    parameters: vec![parameter("mut a", "(bool, u32)")],
+                    return_type: None,
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::collections::HashMap;
+
+use crate::{
+    ast::{NoirStruct, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression},
+    parser::ItemKind,
+    ParsedModule,
+};
+
+/// Returns the name of every struct declared in `module` along with the number of field elements
+/// it's made of once flattened, as when passed as a program input, in declaration order.
+///
+/// Fields, integers and booleans are one field element wide, a `str<N>` is `N` wide, and arrays,
+/// tuples and structs are as wide as their elements or fields combined. Structs declared in
+/// inline submodules aren't included.
+///
+/// A struct whose width isn't a constant isn't returned. This is the case for generic structs,
+/// structs with a field whose type has a generic length or isn't a valid input (such as a slice or
+/// a reference), and structs with a field whose type is a struct which isn't declared in
+/// `module` or isn't returned itself.
+pub fn struct_field_widths(module: &ParsedModule) -> Vec<(String, usize)> {
+    let structs: Vec<_> = module
+        .items
+        .iter()
+        .filter_map(|item| match &item.kind {
+            ItemKind::Struct(noir_struct) => Some(noir_struct),
+            _ => None,
+        })
+        .collect();
+
+    let mut calculator = WidthCalculator {
+        structs: structs
+            .iter()
+            .map(|noir_struct| (noir_struct.name.0.contents.as_str(), *noir_struct))
+            .collect(),
+        widths: HashMap::new(),
+    };
+
+    let widths = structs.iter().filter_map(|noir_struct| {
+        let width = calculator.struct_width(&noir_struct.name.0.contents)?;
+        Some((noir_struct.name.0.contents.clone(), width))
+    });
+    widths.collect()
+}
+
+struct WidthCalculator<'a> {
+    structs: HashMap<&'a str, &'a NoirStruct>,
+    /// The width of each struct already computed. A struct is mapped to `None` while its width is
+    /// being computed, so that recursive structs have no width.
+    widths: HashMap<&'a str, Option<usize>>,
+}
+
+impl<'a> WidthCalculator<'a> {
+    fn struct_width(&mut self, name: &str) -> Option<usize> {
+        let (&name, &noir_struct) = self.structs.get_key_value(name)?;
+        if let Some(width) = self.widths.get(name) {
+            return *width;
+        }
+        if !noir_struct.generics.is_empty() {
+            return None;
+        }
+
+        self.widths.insert(name, None);
+        let width = noir_struct.fields.iter().map(|field| self.type_width(&field.item.typ)).sum();
+        self.widths.insert(name, width);
+        width
+    }
+
+    fn type_width(&mut self, typ: &UnresolvedType) -> Option<usize> {
+        match &typ.typ {
+            UnresolvedTypeData::FieldElement
+            | UnresolvedTypeData::Integer(..)
+            | UnresolvedTypeData::Bool => Some(1),
+            UnresolvedTypeData::Unit => Some(0),
+            UnresolvedTypeData::Array(length, element) => {
+                constant_length(length)?.checked_mul(self.type_width(element)?)
+            }
+            UnresolvedTypeData::String(length) => constant_length(length),
+            UnresolvedTypeData::Tuple(elements) => {
+                elements.iter().map(|element| self.type_width(element)).sum()
+            }
+            UnresolvedTypeData::Parenthesized(typ) => self.type_width(typ),
+            UnresolvedTypeData::Named(path, generics, _) if generics.is_empty() => {
+                self.struct_width(&path.as_ident()?.0.contents)
+            }
+            _ => None,
+        }
+    }
+}
+
+fn constant_length(length: &UnresolvedTypeExpression) -> Option<usize> {
+    match length {
+        UnresolvedTypeExpression::Constant(length, _) => length.try_to_u32()?.try_into().ok(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::struct_field_widths;
+
+    #[test]
+    fn computes_flattened_widths() {
+        let src = "
+        struct Point { x: Field, y: Field }
+        struct Flags { a: bool, b: u8, c: () }
+        struct Line { start: Point, end: Point, label: str<4> }
+        struct Shape { lines: [Line; 3], pairs: [(u32, Flags); 2] }
+        struct Generic<T> { value: T }
+        struct WithGeneric { inner: Generic<Field> }
+        struct WithSlice { values: [Field] }
+        struct WithUnknown { value: Unknown }
+        struct List { next: List }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let widths = struct_field_widths(&module);
+        let widths: Vec<_> = widths.iter().map(|(name, width)| (name.as_str(), *width)).collect();
+        assert_eq!(widths, vec![("Point", 2), ("Flags", 2), ("Line", 8), ("Shape", 30)]);
+    }
+}
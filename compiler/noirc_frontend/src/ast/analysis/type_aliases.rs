@@ -0,0 +1,79 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{NoirTypeAlias, UnresolvedType, Visitor},
+    parser::ParsedSubModule,
+    ParsedModule,
+};
+
+/// Returns the name and target type of every type alias in `module`, in the order they are
+/// declared, including aliases declared in inline submodules.
+///
+/// Names are qualified by the inline submodules they're declared in (e.g. `foo::Bar`). Target
+/// types are returned as written: aliases to other aliases aren't expanded.
+pub fn collect_type_aliases(module: &ParsedModule) -> Vec<(String, UnresolvedType)> {
+    let mut collector = TypeAliasCollector::default();
+    module.accept(&mut collector);
+    collector.type_aliases
+}
+
+#[derive(Default)]
+struct TypeAliasCollector {
+    type_aliases: Vec<(String, UnresolvedType)>,
+    module_path: Vec<String>,
+}
+
+impl Visitor for TypeAliasCollector {
+    fn visit_parsed_submodule(&mut self, submodule: &ParsedSubModule, _: Span) -> bool {
+        self.module_path.push(submodule.name.to_string());
+        submodule.accept_children(self);
+        self.module_path.pop();
+        false
+    }
+
+    fn visit_noir_type_alias(&mut self, type_alias: &NoirTypeAlias, _: Span) -> bool {
+        let mut name = self.module_path.clone();
+        name.push(type_alias.name.to_string());
+        self.type_aliases.push((name.join("::"), type_alias.typ.clone()));
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::collect_type_aliases;
+
+    #[test]
+    fn collects_type_aliases_with_qualified_names() {
+        let src = "
+        type Bytes = [u8; 32];
+
+        fn main() {}
+
+        mod inner {
+            type Pair<T> = (T, T);
+
+            mod nested {
+                type Alias = super::Pair<Field>;
+            }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let type_aliases: Vec<_> = collect_type_aliases(&module)
+            .into_iter()
+            .map(|(name, typ)| (name, typ.to_string()))
+            .collect();
+        assert_eq!(
+            type_aliases,
+            vec![
+                ("Bytes".to_string(), "[u8; 32]".to_string()),
+                ("inner::Pair".to_string(), "(T, T)".to_string()),
+                ("inner::nested::Alias".to_string(), "super::Pair<Field>".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,214 @@
+use std::collections::HashSet;
+
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        AsTraitPath, CastExpression, Expression, Lambda, MethodCallExpression, NoirFunction, Path,
+        PathKind, UnresolvedGeneric, UnresolvedType, UnresolvedTypeExpression, Visitor,
+    },
+    token::{Token, Tokens},
+};
+
+/// Returns the name of every generic declared by `function` which is never used, in the order
+/// they are declared.
+///
+/// A generic is used if it appears in a parameter type, the return type, the where clause or
+/// anywhere in the body: in a type (including array and string lengths), as the first segment of
+/// a path (e.g. `T::default()` or a numeric generic `N` used as a value), or in a `quote`
+/// expression.
+pub fn find_unused_generics(function: &NoirFunction) -> Vec<String> {
+    let mut collector = GenericUseCollector::default();
+    function.accept_children(&mut collector);
+
+    function
+        .def
+        .generics
+        .iter()
+        .filter_map(|generic| match generic {
+            UnresolvedGeneric::Variable(ident) | UnresolvedGeneric::Numeric { ident, .. } => {
+                Some(ident.0.contents.clone())
+            }
+            UnresolvedGeneric::Resolved(..) => None,
+        })
+        .filter(|name| !collector.used.contains(name))
+        .collect()
+}
+
+/// Returns the generics among `generics` which appear in any of `types`, in the order they are
+/// declared.
+pub(crate) fn generics_used_in_types<'a>(
+    generics: &'a [UnresolvedGeneric],
+    types: &[UnresolvedType],
+) -> Vec<&'a UnresolvedGeneric> {
+    let mut collector = GenericUseCollector::default();
+    for typ in types {
+        typ.accept(&mut collector);
+    }
+
+    generics
+        .iter()
+        .filter(|generic| match generic {
+            UnresolvedGeneric::Variable(ident) | UnresolvedGeneric::Numeric { ident, .. } => {
+                collector.used.contains(&ident.0.contents)
+            }
+            UnresolvedGeneric::Resolved(..) => false,
+        })
+        .collect()
+}
+
+/// Returns every name used in `expression` or in any of `types` in a position where it could
+/// refer to a generic, as [find_unused_generics] finds them. This includes `Self`.
+pub(crate) fn names_used_as_generics(
+    expression: &Expression,
+    types: &[&UnresolvedType],
+) -> HashSet<String> {
+    let mut collector = GenericUseCollector::default();
+    expression.accept(&mut collector);
+    for typ in types {
+        typ.accept(&mut collector);
+    }
+    collector.used
+}
+
+#[derive(Default)]
+struct GenericUseCollector {
+    used: HashSet<String>,
+}
+
+impl GenericUseCollector {
+    fn use_path(&mut self, path: &Path) {
+        if path.kind == PathKind::Plain {
+            if let Some(name) = path.first_name() {
+                self.used.insert(name.to_string());
+            }
+        }
+
+        // Turbofish generics, as in `foo::<T>()`
+        for segment in &path.segments {
+            for typ in segment.generics.iter().flatten() {
+                typ.accept(self);
+            }
+        }
+    }
+
+    fn use_type_expression(&mut self, expression: &UnresolvedTypeExpression) {
+        match expression {
+            UnresolvedTypeExpression::Variable(path) => self.use_path(path),
+            UnresolvedTypeExpression::Constant(..) => (),
+            UnresolvedTypeExpression::BinaryOperation(lhs, _, rhs, _) => {
+                self.use_type_expression(lhs);
+                self.use_type_expression(rhs);
+            }
+            UnresolvedTypeExpression::AsTraitPath(as_trait_path) => {
+                as_trait_path.accept(Span::default(), self);
+            }
+        }
+    }
+}
+
+impl Visitor for GenericUseCollector {
+    fn visit_path(&mut self, path: &Path) {
+        self.use_path(path);
+    }
+
+    fn visit_array_type(
+        &mut self,
+        length: &UnresolvedTypeExpression,
+        _: &UnresolvedType,
+        _: Span,
+    ) -> bool {
+        self.use_type_expression(length);
+        true
+    }
+
+    fn visit_expression_type(&mut self, expression: &UnresolvedTypeExpression, _: Span) {
+        self.use_type_expression(expression);
+    }
+
+    fn visit_string_type(&mut self, length: &UnresolvedTypeExpression, _: Span) {
+        self.use_type_expression(length);
+    }
+
+    fn visit_format_string_type(
+        &mut self,
+        length: &UnresolvedTypeExpression,
+        _: &UnresolvedType,
+        _: Span,
+    ) -> bool {
+        self.use_type_expression(length);
+        true
+    }
+
+    fn visit_as_trait_path(&mut self, as_trait_path: &AsTraitPath, _: Span) -> bool {
+        as_trait_path.typ.accept(self);
+        true
+    }
+
+    fn visit_cast_expression(&mut self, cast: &CastExpression, _: Span) -> bool {
+        cast.r#type.accept(self);
+        true
+    }
+
+    fn visit_lambda(&mut self, lambda: &Lambda, _: Span) -> bool {
+        lambda.return_type.accept(self);
+        true
+    }
+
+    fn visit_method_call_expression(&mut self, call: &MethodCallExpression, _: Span) -> bool {
+        for typ in call.generics.iter().flatten() {
+            typ.accept(self);
+        }
+        true
+    }
+
+    fn visit_quote(&mut self, tokens: &Tokens) {
+        for token in &tokens.0 {
+            if let Token::Ident(name) = token.token() {
+                self.used.insert(name.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{parse_program, ItemKind};
+
+    use super::find_unused_generics;
+
+    fn unused_generics_of_functions(src: &str) -> Vec<Vec<String>> {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        module
+            .items
+            .iter()
+            .map(|item| match &item.kind {
+                ItemKind::Function(function) => find_unused_generics(function),
+                _ => panic!("Expected a function"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn finds_unused_generics() {
+        let src = "
+        fn unused_type<T, U>(x: T) -> T { x }
+
+        fn used_in_body<T, let N: u32>() -> Field {
+            let x: T = T::default();
+            let array = [0; N];
+            array.len() as Field
+        }
+
+        fn used_in_length<let N: u32, let M: u32>(x: [Field; N + 1]) -> str<M> { dep::foo(x) }
+
+        fn used_in_turbofish_and_cast<A, B, C>() -> Field { foo::<A>().bar::<B>() as C }
+        ";
+        assert_eq!(
+            unused_generics_of_functions(src),
+            vec![vec!["U".to_string()], vec![], vec![], vec![]]
+        );
+    }
+}
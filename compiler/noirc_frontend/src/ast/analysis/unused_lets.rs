@@ -0,0 +1,142 @@
+use noirc_errors::Span;
+
+use crate::{ast::Ident, ParsedModule};
+
+use super::scope_tracker::{Binding, ScopeHandler, ScopeTracker};
+
+/// Returns the span of every variable bound by a local `let` statement in `module` which is never
+/// referenced afterwards in its scope.
+///
+/// Shadowing is taken into account, so a reference always resolves to the innermost, most recent
+/// binding with that name (which may be a function, lambda or `for` loop parameter). Bindings whose
+/// name starts with an underscore are never reported. Being assigned to, or appearing anywhere
+/// in a `quote` expression, counts as a use, as does being interpolated in a format string (as in
+/// `f"{x}"`).
+pub fn find_unused_lets(module: &ParsedModule) -> Vec<Span> {
+    let mut tracker = ScopeTracker::new(UnusedLetFinder::default());
+    module.accept(&mut tracker);
+
+    let mut unused = tracker.handler.unused;
+    unused.sort_by_key(|span| span.start());
+    unused
+}
+
+struct Usage {
+    /// Whether this binding should be reported if unused. This is `false` for parameters.
+    is_reported: bool,
+    is_used: bool,
+}
+
+#[derive(Default)]
+struct UnusedLetFinder {
+    unused: Vec<Span>,
+}
+
+impl ScopeHandler for UnusedLetFinder {
+    type Data = Usage;
+
+    fn declare(&mut self, ident: &Ident, is_let: bool, _: &[Vec<Binding<Usage>>]) -> Usage {
+        Usage { is_reported: is_let && !ident.0.contents.starts_with('_'), is_used: false }
+    }
+
+    fn exit_scope(&mut self, bindings: Vec<Binding<Usage>>) {
+        let unused = bindings.into_iter().filter(|binding| {
+            let usage = &binding.data;
+            usage.is_reported && !usage.is_used
+        });
+        self.unused.extend(unused.map(|binding| binding.span));
+    }
+
+    fn use_binding(&mut self, binding: &mut Binding<Usage>) {
+        binding.data.is_used = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::find_unused_lets;
+
+    /// Returns the start offset of every unused `let` binding in `src`.
+    fn unused_let_offsets(src: &str) -> Vec<usize> {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        find_unused_lets(&module).into_iter().map(|span| span.start() as usize).collect()
+    }
+
+    #[test]
+    fn finds_unused_lets() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let used = x;
+            let (unused, _ignored) = (1, 2);
+            let mut assigned = 0;
+            assigned = 1;
+            let captured = 3;
+            let f = |y: Field| y + captured;
+            for i in 0..used { assert(i != 0); }
+            f(used)
+        }
+        ";
+        assert_eq!(unused_let_offsets(src), vec![src.find("unused").unwrap()]);
+    }
+
+    #[test]
+    fn handles_shadowing() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let a = x;
+            let a = a + 1;
+            let b = 1;
+            let b = 2;
+            let c = 3;
+            let f = |c: Field| c;
+            {
+                let x = 4;
+                f(a + b)
+            }
+        }
+        ";
+        assert_eq!(
+            unused_let_offsets(src),
+            vec![
+                src.find("b = 1").unwrap(),
+                src.find("c = 3").unwrap(),
+                src.find("x = 4").unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn uses_in_nested_blocks_and_quotes_count() {
+        let src = "
+        fn main() -> pub Field {
+            let a = 1;
+            let b = 2;
+            let c = if true { a } else { 0 };
+            comptime {
+                let q = quote { $b };
+            }
+            c
+        }
+        ";
+        assert_eq!(unused_let_offsets(src), vec![src.find("q = ").unwrap()]);
+    }
+
+    #[test]
+    fn uses_in_format_strings_count() {
+        let src = r#"
+        fn main() {
+            let x = 1;
+            let y = 2;
+            let z = 3;
+            println(f"{x} and { y }");
+        }
+        "#;
+        assert_eq!(
+            unused_let_offsets(src),
+            vec![src.find("y = 2").unwrap(), src.find("z = 3").unwrap()]
+        );
+    }
+}
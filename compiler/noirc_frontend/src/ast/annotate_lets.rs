@@ -0,0 +1,103 @@
+use crate::{
+    ast::{
+        scopes::{walk_module, ScopedRewriter},
+        Expression, LetStatement, UnresolvedType, UnresolvedTypeData,
+    },
+    ParsedModule,
+};
+
+/// Gives a type annotation to every `let` statement and global of `module` which doesn't have
+/// one, using the type `infer` returns for its initializer. Lets for which `infer` returns `None`
+/// are left unannotated, as are lets which already have an annotation.
+///
+/// As types aren't known before elaboration, `infer` is expected to work from the shape of the
+/// initializer (e.g. a literal or a constructor). When a let is nested in another's initializer,
+/// the nested one is annotated first.
+pub fn annotate_lets(
+    module: &mut ParsedModule,
+    infer: impl Fn(&Expression) -> Option<UnresolvedType>,
+) {
+    walk_module(module, &mut LetAnnotator { infer });
+}
+
+struct LetAnnotator<F> {
+    infer: F,
+}
+
+impl<F: Fn(&Expression) -> Option<UnresolvedType>> ScopedRewriter for LetAnnotator<F> {
+    fn exit_let(&mut self, let_statement: &mut LetStatement) {
+        if !matches!(let_statement.r#type.typ, UnresolvedTypeData::Unspecified) {
+            return;
+        }
+
+        if let Some(typ) = (self.infer)(&let_statement.expression) {
+            let_statement.r#type = typ;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+    use noirc_errors::Span;
+
+    use crate::{
+        ast::{Expression, ExpressionKind, Literal, UnresolvedType, UnresolvedTypeData},
+        parser::{parse_program, ItemKind},
+    };
+
+    use super::annotate_lets;
+
+    /// Infers the type of integer and boolean literals, and of tuples of those.
+    fn infer_literal_type(expression: &Expression) -> Option<UnresolvedType> {
+        let typ = match &expression.kind {
+            ExpressionKind::Literal(Literal::Integer(..)) => UnresolvedTypeData::FieldElement,
+            ExpressionKind::Literal(Literal::Bool(_)) => UnresolvedTypeData::Bool,
+            ExpressionKind::Tuple(elements) => {
+                let types: Option<Vec<_>> = elements.iter().map(infer_literal_type).collect();
+                UnresolvedTypeData::Tuple(types?)
+            }
+            _ => return None,
+        };
+        Some(UnresolvedType { typ, span: Span::default() })
+    }
+
+    #[test]
+    fn annotates_only_unspecified_lets() {
+        let src = "
+        global G = 3;
+
+        fn main(x: Field) {
+            let a = 1;
+            let b: u8 = 2;
+            let (c, d) = (1, true);
+            let (e, f) = (x, true);
+            let g = { let h = false; h };
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        annotate_lets(&mut module, infer_literal_type);
+
+        let ItemKind::Global(global, _) = &module.items[0].kind else {
+            panic!("Expected a global");
+        };
+        assert_eq!(global.to_string(), "let G: Field = 3");
+
+        let ItemKind::Function(main) = &module.items[1].kind else {
+            panic!("Expected a function");
+        };
+        let body = vecmap(&main.def.body.statements, |statement| statement.kind.to_string());
+        assert_eq!(
+            body,
+            vec![
+                "let a: Field = 1",
+                "let b: u8 = 2",
+                "let (c, d): (Field, bool) = (1, true)",
+                "let (e, f) = (x, true)",
+                "let g = {\n    let h: bool = false\n    h\n}",
+            ]
+        );
+    }
+}
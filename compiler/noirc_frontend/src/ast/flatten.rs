@@ -0,0 +1,217 @@
+use std::collections::HashSet;
+
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        scopes::{walk_module, ScopedRewriter},
+        BlockExpression, Expression, ExpressionKind, Ident, Path, Pattern, Statement,
+        StatementKind, Visitor,
+    },
+    token::{Token, Tokens},
+    ParsedModule,
+};
+
+/// Merges the statements of every block of `module` which is used as a statement of another
+/// block, as in `{ foo(); { bar(); } }`, into the statements of that outer block.
+///
+/// A block is only merged if doing so can't change what a name refers to: the variables its
+/// `let` statements declare must not be referenced (including being assigned to, or appearing in
+/// a format string or a `quote` expression) by any statement following it in the outer block.
+/// A merged block's tail expression becomes a statement ending with a semicolon, and gets the
+/// default span, unless the block was the outer block's own tail expression. `unsafe` and
+/// `comptime` blocks are never merged into their outer block.
+pub fn flatten_blocks(module: &mut ParsedModule) {
+    walk_module(module, &mut BlockFlattener);
+}
+
+struct BlockFlattener;
+
+impl ScopedRewriter for BlockFlattener {
+    fn exit_block(&mut self, block: &mut BlockExpression) {
+        // Going backwards, so that the statements following a block are already in their final
+        // form when checking whether it can be merged.
+        for index in (0..block.statements.len()).rev() {
+            let (StatementKind::Expression(expression) | StatementKind::Semi(expression)) =
+                &block.statements[index].kind
+            else {
+                continue;
+            };
+            let ExpressionKind::Block(inner_block) = &expression.kind else {
+                continue;
+            };
+            if !can_be_merged(inner_block, &block.statements[index + 1..]) {
+                continue;
+            }
+
+            let is_tail = index + 1 == block.statements.len()
+                && matches!(block.statements[index].kind, StatementKind::Expression(_));
+            let statement = block.statements.remove(index);
+            let (StatementKind::Expression(expression) | StatementKind::Semi(expression)) =
+                statement.kind
+            else {
+                unreachable!("Checked to be an expression statement above");
+            };
+            let ExpressionKind::Block(mut inner_block) = expression.kind else {
+                unreachable!("Checked to be a block above");
+            };
+
+            if !is_tail {
+                discard_tail_value(&mut inner_block);
+            }
+            block.statements.splice(index..index, inner_block.statements);
+        }
+    }
+}
+
+/// Returns whether none of the variables declared by `block` are referenced by `following`.
+fn can_be_merged(block: &BlockExpression, following: &[Statement]) -> bool {
+    let mut declared = HashSet::new();
+    for statement in &block.statements {
+        declare_statement(statement, &mut declared);
+    }
+    if declared.is_empty() {
+        return true;
+    }
+
+    let mut collector = NameCollector::default();
+    for statement in following {
+        statement.accept(&mut collector);
+    }
+    collector.names.is_disjoint(&declared)
+}
+
+fn declare_statement(statement: &Statement, declared: &mut HashSet<String>) {
+    match &statement.kind {
+        StatementKind::Let(let_statement) => declare_pattern(&let_statement.pattern, declared),
+        StatementKind::Comptime(statement) => declare_statement(statement, declared),
+        _ => (),
+    }
+}
+
+fn declare_pattern(pattern: &Pattern, declared: &mut HashSet<String>) {
+    match pattern {
+        Pattern::Identifier(ident) => {
+            declared.insert(ident.0.contents.clone());
+        }
+        Pattern::Mutable(pattern, ..) => declare_pattern(pattern, declared),
+        Pattern::Tuple(patterns, _) => {
+            for pattern in patterns {
+                declare_pattern(pattern, declared);
+            }
+        }
+        Pattern::Struct(_, fields, _) => {
+            for (_, pattern) in fields {
+                declare_pattern(pattern, declared);
+            }
+        }
+        Pattern::Interned(..) => (),
+    }
+}
+
+/// Turns the tail expression of `block`, if any, into a statement ending with a semicolon.
+fn discard_tail_value(block: &mut BlockExpression) {
+    if let Some(statement) = block.statements.last_mut() {
+        if let StatementKind::Expression(expression) = &statement.kind {
+            statement.kind = StatementKind::Semi(expression.clone());
+            statement.span = Span::default();
+        }
+    }
+}
+
+/// Collects every name which may refer to a local variable.
+#[derive(Default)]
+struct NameCollector {
+    names: HashSet<String>,
+}
+
+impl Visitor for NameCollector {
+    fn visit_variable(&mut self, path: &Path, _: Span) -> bool {
+        if let Some(ident) = path.as_ident() {
+            self.names.insert(ident.0.contents.clone());
+        }
+        true
+    }
+
+    fn visit_lvalue_ident(&mut self, ident: &Ident) {
+        self.names.insert(ident.0.contents.clone());
+    }
+
+    fn visit_literal_fmt_str(&mut self, string: &str) {
+        let words = string.split(|char: char| !char.is_ascii_alphanumeric() && char != '_');
+        self.names.extend(words.filter(|word| !word.is_empty()).map(ToString::to_string));
+    }
+
+    fn visit_quote(&mut self, tokens: &Tokens) {
+        for token in &tokens.0 {
+            if let Token::Ident(name) = token.token() {
+                self.names.insert(name.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+
+    use crate::{
+        parser::{parse_program, ItemKind},
+        ParsedModule,
+    };
+
+    use super::flatten_blocks;
+
+    fn parse(src: &str) -> ParsedModule {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        module
+    }
+
+    fn body_strings(module: &ParsedModule) -> Vec<String> {
+        let ItemKind::Function(function) = &module.items[0].kind else {
+            panic!("Expected a function");
+        };
+        vecmap(&function.def.body.statements, |statement| statement.kind.to_string())
+    }
+
+    #[test]
+    fn flattens_grouping_blocks() {
+        let mut module = parse(
+            "
+            fn main(x: Field) -> pub Field {
+                { foo(x); { bar(x); } }
+                { let a = 1; baz(a) };
+                let y = { { x + 1 } };
+                y
+            }
+            ",
+        );
+        flatten_blocks(&mut module);
+
+        assert_eq!(
+            body_strings(&module),
+            vec!["foo(x);", "bar(x);", "let a = 1", "baz(a);", "let y = {\n    (x + 1)\n}", "y"]
+        );
+    }
+
+    #[test]
+    fn does_not_flatten_blocks_whose_variables_are_referenced_afterwards() {
+        let src = "
+            fn main(x: Field) -> pub Field {
+                let mut y = x;
+                { let x = 1; foo(x); }
+                { let mut y = 2; y = y + 1; foo(y); }
+                { let z = 3; foo(z); }
+                y = 3;
+                println(f\"{z}\");
+                x + y
+            }
+            ";
+        let mut module = parse(src);
+        let expected = body_strings(&module);
+
+        flatten_blocks(&mut module);
+        assert_eq!(body_strings(&module), expected);
+    }
+}
@@ -0,0 +1,638 @@
+use std::cell::RefCell;
+
+use iter_extended::vecmap;
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        ArrayLiteral, AssignStatement, BinaryOp, BlockExpression, CallExpression, CastExpression,
+        ConstrainStatement, ConstructorExpression, Documented, Expression, ExpressionKind,
+        ForBounds, ForLoopStatement, ForRange, IfExpression, IndexExpression, InfixExpression,
+        LValue, Lambda, LetStatement, Literal, MemberAccessExpression, MethodCallExpression,
+        NoirFunction, NoirTrait, NoirTraitImpl, Path, PathKind, PathSegment, PrefixExpression,
+        Statement, StatementKind, TraitImplItem, TraitImplItemKind, TraitItem, TypeImpl,
+    },
+    parser::{Item, ItemKind, ParsedSubModule},
+    token::{MetaAttribute, SecondaryAttribute},
+    ParsedModule,
+};
+
+/// Returns a new module where every expression of `module` has been replaced by the result of
+/// applying `f` to it.
+///
+/// Expressions are visited in the same positions as the AST [Visitor][crate::ast::Visitor]
+/// (including expressions in global initializers, trait constants and meta attribute arguments)
+/// and are rewritten bottom-up: `f` receives an expression whose subexpressions have already
+/// been mapped. Any part of the module which doesn't contain an expression is moved as is.
+///
+/// This runs in O(n) in the size of the module but allocates a new tree.
+pub fn map_module(module: ParsedModule, f: impl Fn(Expression) -> Expression) -> ParsedModule {
+    ExpressionMapper { f }.map_module(module)
+}
+
+/// Returns the result of applying `f` to `expression` and to every expression nested in it,
+/// bottom-up like [map_module].
+pub(crate) fn map_expression(
+    expression: Expression,
+    f: impl Fn(Expression) -> Expression,
+) -> Expression {
+    ExpressionMapper { f }.map_expression(expression)
+}
+
+/// Applies `f` in place to every integer literal expression in `module`, in the same positions
+/// and order as [map_module].
+///
+/// This includes literals used as array repeat lengths, indices and call arguments. Integer
+/// literals in type positions (such as the length in `[u8; 3]`) aren't expressions so they're
+/// left untouched.
+pub fn map_integer_literals(module: &mut ParsedModule, f: impl FnMut(&mut Literal)) {
+    // `f` is never reentered as each expression is mapped only once its children have been.
+    let f = RefCell::new(f);
+    let mapped = map_module(std::mem::take(module), |mut expression| {
+        if let ExpressionKind::Literal(literal @ Literal::Integer(..)) = &mut expression.kind {
+            (f.borrow_mut())(literal);
+        }
+        expression
+    });
+    *module = mapped;
+}
+
+/// Rewrites every method call `x.foo::<T>(a, b)` in `module` into the function call
+/// `foo::<T>(x, a, b)`, whose receiver is passed as the first argument.
+///
+/// Chained calls are rewritten from the inside out, so `x.foo().bar()` becomes `bar(foo(x))`.
+/// The call expression and the path to the function get the default span; the method name,
+/// receiver and arguments keep theirs.
+pub fn desugar_method_calls(module: &mut ParsedModule) {
+    let mapped = map_module(std::mem::take(module), |expression| match expression.kind {
+        ExpressionKind::MethodCall(method_call) => {
+            let method_call = *method_call;
+            let segment = PathSegment {
+                ident: method_call.method_name,
+                generics: method_call.generics,
+                span: Span::default(),
+            };
+            let func =
+                Path { segments: vec![segment], kind: PathKind::Plain, span: Span::default() };
+            let func = Expression::new(ExpressionKind::Variable(func), Span::default());
+
+            let mut arguments = Vec::with_capacity(method_call.arguments.len() + 1);
+            arguments.push(method_call.object);
+            arguments.extend(method_call.arguments);
+
+            let call = CallExpression {
+                func: Box::new(func),
+                arguments,
+                is_macro_call: method_call.is_macro_call,
+            };
+            Expression::new(ExpressionKind::Call(Box::new(call)), Span::default())
+        }
+        kind => Expression { kind, span: expression.span },
+    });
+    *module = mapped;
+}
+
+/// Rewrites every infix expression `lhs op rhs` in `module` whose operator is `op` into the call
+/// `func_path(lhs, rhs)`, e.g. to route arithmetic through checked helper functions.
+///
+/// Nested expressions are rewritten from the inside out, so with `*` as `op`, `a * b * c` becomes
+/// `func_path(func_path(a, b), c)`. As call arguments are evaluated left to right, the operands
+/// are still evaluated in the same order. The call expression and the variable referring to
+/// `func_path` get the default span; the operands keep theirs.
+pub fn rewrite_infix_to_calls(module: &mut ParsedModule, op: BinaryOp, func_path: Path) {
+    let mapped = map_module(std::mem::take(module), |expression| match expression.kind {
+        ExpressionKind::Infix(infix) if infix.operator == op => {
+            let infix = *infix;
+            let func = ExpressionKind::Variable(func_path.clone());
+            let call = CallExpression {
+                func: Box::new(Expression::new(func, Span::default())),
+                arguments: vec![infix.lhs, infix.rhs],
+                is_macro_call: false,
+            };
+            Expression::new(ExpressionKind::Call(Box::new(call)), Span::default())
+        }
+        kind => Expression { kind, span: expression.span },
+    });
+    *module = mapped;
+}
+
+struct ExpressionMapper<F> {
+    f: F,
+}
+
+impl<F: Fn(Expression) -> Expression> ExpressionMapper<F> {
+    fn map_module(&self, module: ParsedModule) -> ParsedModule {
+        let items = vecmap(module.items, |item| self.map_item(item));
+        ParsedModule { items, inner_doc_comments: module.inner_doc_comments }
+    }
+
+    fn map_item(&self, item: Item) -> Item {
+        let kind = match item.kind {
+            ItemKind::Function(function) => ItemKind::Function(self.map_function(function)),
+            ItemKind::Struct(mut noir_struct) => {
+                noir_struct.attributes = self.map_attributes(noir_struct.attributes);
+                ItemKind::Struct(noir_struct)
+            }
+            ItemKind::Trait(noir_trait) => ItemKind::Trait(self.map_trait(noir_trait)),
+            ItemKind::TraitImpl(trait_impl) => ItemKind::TraitImpl(self.map_trait_impl(trait_impl)),
+            ItemKind::Impl(type_impl) => ItemKind::Impl(self.map_type_impl(type_impl)),
+            ItemKind::Global(let_statement, visibility) => {
+                ItemKind::Global(self.map_let_statement(let_statement), visibility)
+            }
+            ItemKind::ModuleDecl(mut module_declaration) => {
+                module_declaration.outer_attributes =
+                    self.map_attributes(module_declaration.outer_attributes);
+                ItemKind::ModuleDecl(module_declaration)
+            }
+            ItemKind::Submodules(submodule) => {
+                ItemKind::Submodules(self.map_parsed_submodule(submodule))
+            }
+            ItemKind::InnerAttribute(attribute) => {
+                ItemKind::InnerAttribute(self.map_attribute(attribute))
+            }
+            kind @ (ItemKind::Import(..) | ItemKind::TypeAlias(_)) => kind,
+        };
+        Item { kind, ..item }
+    }
+
+    fn map_parsed_submodule(&self, submodule: ParsedSubModule) -> ParsedSubModule {
+        ParsedSubModule {
+            contents: self.map_module(submodule.contents),
+            outer_attributes: self.map_attributes(submodule.outer_attributes),
+            ..submodule
+        }
+    }
+
+    fn map_function(&self, mut function: NoirFunction) -> NoirFunction {
+        function.def.attributes.secondary = self.map_attributes(function.def.attributes.secondary);
+        function.def.body = self.map_block(function.def.body);
+        function
+    }
+
+    fn map_trait(&self, noir_trait: NoirTrait) -> NoirTrait {
+        let items =
+            vecmap(noir_trait.items, |item| self.map_documented(item, Self::map_trait_item));
+        let attributes = self.map_attributes(noir_trait.attributes);
+        NoirTrait { items, attributes, ..noir_trait }
+    }
+
+    fn map_trait_item(&self, item: TraitItem) -> TraitItem {
+        match item {
+            TraitItem::Function {
+                is_unconstrained,
+                visibility,
+                is_comptime,
+                name,
+                generics,
+                parameters,
+                return_type,
+                where_clause,
+                body,
+            } => TraitItem::Function {
+                is_unconstrained,
+                visibility,
+                is_comptime,
+                name,
+                generics,
+                parameters,
+                return_type,
+                where_clause,
+                body: body.map(|body| self.map_block(body)),
+            },
+            TraitItem::Constant { name, typ, default_value } => TraitItem::Constant {
+                name,
+                typ,
+                default_value: default_value.map(|value| self.map_expression(value)),
+            },
+            TraitItem::Type { name } => TraitItem::Type { name },
+        }
+    }
+
+    fn map_trait_impl(&self, trait_impl: NoirTraitImpl) -> NoirTraitImpl {
+        let items =
+            vecmap(trait_impl.items, |item| self.map_documented(item, Self::map_trait_impl_item));
+        NoirTraitImpl { items, ..trait_impl }
+    }
+
+    fn map_trait_impl_item(&self, item: TraitImplItem) -> TraitImplItem {
+        let kind = match item.kind {
+            TraitImplItemKind::Function(function) => {
+                TraitImplItemKind::Function(self.map_function(function))
+            }
+            TraitImplItemKind::Constant(name, typ, expression) => {
+                TraitImplItemKind::Constant(name, typ, self.map_expression(expression))
+            }
+            kind @ TraitImplItemKind::Type { .. } => kind,
+        };
+        TraitImplItem { kind, span: item.span }
+    }
+
+    fn map_type_impl(&self, type_impl: TypeImpl) -> TypeImpl {
+        let methods = vecmap(type_impl.methods, |(method, span)| {
+            (self.map_documented(method, Self::map_function), span)
+        });
+        TypeImpl { methods, ..type_impl }
+    }
+
+    fn map_documented<T>(
+        &self,
+        documented: Documented<T>,
+        map: impl Fn(&Self, T) -> T,
+    ) -> Documented<T> {
+        Documented { item: map(self, documented.item), doc_comments: documented.doc_comments }
+    }
+
+    fn map_attributes(&self, attributes: Vec<SecondaryAttribute>) -> Vec<SecondaryAttribute> {
+        vecmap(attributes, |attribute| self.map_attribute(attribute))
+    }
+
+    fn map_attribute(&self, attribute: SecondaryAttribute) -> SecondaryAttribute {
+        match attribute {
+            SecondaryAttribute::Meta(meta_attribute) => {
+                let arguments = self.map_expressions(meta_attribute.arguments);
+                SecondaryAttribute::Meta(MetaAttribute { arguments, ..meta_attribute })
+            }
+            attribute => attribute,
+        }
+    }
+
+    fn map_block(&self, block: BlockExpression) -> BlockExpression {
+        BlockExpression {
+            statements: vecmap(block.statements, |statement| self.map_statement(statement)),
+        }
+    }
+
+    fn map_statement(&self, statement: Statement) -> Statement {
+        let kind = match statement.kind {
+            StatementKind::Let(let_statement) => {
+                StatementKind::Let(self.map_let_statement(let_statement))
+            }
+            StatementKind::Constrain(constrain_statement) => {
+                StatementKind::Constrain(ConstrainStatement {
+                    arguments: self.map_expressions(constrain_statement.arguments),
+                    ..constrain_statement
+                })
+            }
+            StatementKind::Expression(expression) => {
+                StatementKind::Expression(self.map_expression(expression))
+            }
+            StatementKind::Assign(assign_statement) => StatementKind::Assign(AssignStatement {
+                lvalue: self.map_lvalue(assign_statement.lvalue),
+                expression: self.map_expression(assign_statement.expression),
+            }),
+            StatementKind::For(for_loop) => {
+                let range = match for_loop.range {
+                    ForRange::Range(ForBounds { start, end, inclusive }) => {
+                        ForRange::Range(ForBounds {
+                            start: self.map_expression(start),
+                            end: self.map_expression(end),
+                            inclusive,
+                        })
+                    }
+                    ForRange::Array(expression) => ForRange::Array(self.map_expression(expression)),
+                };
+                let block = self.map_expression(for_loop.block);
+                StatementKind::For(ForLoopStatement { range, block, ..for_loop })
+            }
+            StatementKind::Comptime(statement) => {
+                StatementKind::Comptime(Box::new(self.map_statement(*statement)))
+            }
+            StatementKind::Semi(expression) => StatementKind::Semi(self.map_expression(expression)),
+            kind @ (StatementKind::Break
+            | StatementKind::Continue
+            | StatementKind::Interned(_)
+            | StatementKind::Error) => kind,
+        };
+        Statement { kind, span: statement.span }
+    }
+
+    fn map_let_statement(&self, let_statement: LetStatement) -> LetStatement {
+        LetStatement {
+            expression: self.map_expression(let_statement.expression),
+            attributes: self.map_attributes(let_statement.attributes),
+            ..let_statement
+        }
+    }
+
+    fn map_lvalue(&self, lvalue: LValue) -> LValue {
+        match lvalue {
+            LValue::MemberAccess { object, field_name, span } => LValue::MemberAccess {
+                object: Box::new(self.map_lvalue(*object)),
+                field_name,
+                span,
+            },
+            LValue::Index { array, index, span } => LValue::Index {
+                array: Box::new(self.map_lvalue(*array)),
+                index: self.map_expression(index),
+                span,
+            },
+            LValue::Dereference(lvalue, span) => {
+                LValue::Dereference(Box::new(self.map_lvalue(*lvalue)), span)
+            }
+            lvalue @ (LValue::Ident(_) | LValue::Interned(..)) => lvalue,
+        }
+    }
+
+    fn map_expressions(&self, expressions: Vec<Expression>) -> Vec<Expression> {
+        vecmap(expressions, |expression| self.map_expression(expression))
+    }
+
+    fn map_boxed_expression(&self, expression: Box<Expression>) -> Box<Expression> {
+        Box::new(self.map_expression(*expression))
+    }
+
+    fn map_expression(&self, expression: Expression) -> Expression {
+        let kind = match expression.kind {
+            ExpressionKind::Literal(literal) => ExpressionKind::Literal(self.map_literal(literal)),
+            ExpressionKind::Block(block) => ExpressionKind::Block(self.map_block(block)),
+            ExpressionKind::Prefix(prefix) => ExpressionKind::Prefix(Box::new(PrefixExpression {
+                operator: prefix.operator,
+                rhs: self.map_expression(prefix.rhs),
+            })),
+            ExpressionKind::Index(index) => ExpressionKind::Index(Box::new(IndexExpression {
+                collection: self.map_expression(index.collection),
+                index: self.map_expression(index.index),
+            })),
+            ExpressionKind::Call(call) => ExpressionKind::Call(Box::new(CallExpression {
+                func: self.map_boxed_expression(call.func),
+                arguments: self.map_expressions(call.arguments),
+                is_macro_call: call.is_macro_call,
+            })),
+            ExpressionKind::MethodCall(method_call) => {
+                let method_call = *method_call;
+                ExpressionKind::MethodCall(Box::new(MethodCallExpression {
+                    object: self.map_expression(method_call.object),
+                    arguments: self.map_expressions(method_call.arguments),
+                    ..method_call
+                }))
+            }
+            ExpressionKind::Constructor(constructor) => {
+                let constructor = *constructor;
+                let fields = vecmap(constructor.fields, |(name, expression)| {
+                    (name, self.map_expression(expression))
+                });
+                ExpressionKind::Constructor(Box::new(ConstructorExpression {
+                    fields,
+                    ..constructor
+                }))
+            }
+            ExpressionKind::MemberAccess(member_access) => {
+                ExpressionKind::MemberAccess(Box::new(MemberAccessExpression {
+                    lhs: self.map_expression(member_access.lhs),
+                    rhs: member_access.rhs,
+                }))
+            }
+            ExpressionKind::Cast(cast) => ExpressionKind::Cast(Box::new(CastExpression {
+                lhs: self.map_expression(cast.lhs),
+                r#type: cast.r#type,
+            })),
+            ExpressionKind::Infix(infix) => ExpressionKind::Infix(Box::new(InfixExpression {
+                lhs: self.map_expression(infix.lhs),
+                operator: infix.operator,
+                rhs: self.map_expression(infix.rhs),
+            })),
+            ExpressionKind::If(if_expression) => ExpressionKind::If(Box::new(IfExpression {
+                condition: self.map_expression(if_expression.condition),
+                consequence: self.map_expression(if_expression.consequence),
+                alternative: if_expression
+                    .alternative
+                    .map(|alternative| self.map_expression(alternative)),
+            })),
+            ExpressionKind::Tuple(expressions) => {
+                ExpressionKind::Tuple(self.map_expressions(expressions))
+            }
+            ExpressionKind::Lambda(lambda) => ExpressionKind::Lambda(Box::new(Lambda {
+                parameters: lambda.parameters,
+                return_type: lambda.return_type,
+                body: self.map_expression(lambda.body),
+            })),
+            ExpressionKind::Parenthesized(expression) => {
+                ExpressionKind::Parenthesized(self.map_boxed_expression(expression))
+            }
+            ExpressionKind::Unquote(expression) => {
+                ExpressionKind::Unquote(self.map_boxed_expression(expression))
+            }
+            ExpressionKind::Comptime(block, span) => {
+                ExpressionKind::Comptime(self.map_block(block), span)
+            }
+            ExpressionKind::Unsafe(block, span) => {
+                ExpressionKind::Unsafe(self.map_block(block), span)
+            }
+            kind @ (ExpressionKind::Variable(_)
+            | ExpressionKind::Quote(_)
+            | ExpressionKind::AsTraitPath(_)
+            | ExpressionKind::TypePath(_)
+            | ExpressionKind::Resolved(_)
+            | ExpressionKind::Interned(_)
+            | ExpressionKind::InternedStatement(_)
+            | ExpressionKind::Error) => kind,
+        };
+        (self.f)(Expression { kind, span: expression.span })
+    }
+
+    fn map_literal(&self, literal: Literal) -> Literal {
+        match literal {
+            Literal::Array(array_literal) => Literal::Array(self.map_array_literal(array_literal)),
+            Literal::Slice(array_literal) => Literal::Slice(self.map_array_literal(array_literal)),
+            literal @ (Literal::Bool(_)
+            | Literal::Integer(..)
+            | Literal::Str(_)
+            | Literal::RawStr(..)
+            | Literal::FmtStr(_)
+            | Literal::Unit) => literal,
+        }
+    }
+
+    fn map_array_literal(&self, array_literal: ArrayLiteral) -> ArrayLiteral {
+        match array_literal {
+            ArrayLiteral::Standard(elements) => {
+                ArrayLiteral::Standard(self.map_expressions(elements))
+            }
+            ArrayLiteral::Repeated { repeated_element, length } => ArrayLiteral::Repeated {
+                repeated_element: self.map_boxed_expression(repeated_element),
+                length: self.map_boxed_expression(length),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use acvm::{AcirField, FieldElement};
+    use iter_extended::vecmap;
+    use noirc_errors::{Span, Spanned};
+
+    use crate::{
+        ast::{
+            BinaryOpKind, Expression, ExpressionKind, Ident, Literal, Path, StatementKind, Visitor,
+        },
+        parser::{parse_program, ItemKind},
+        ParsedModule,
+    };
+
+    use super::{desugar_method_calls, map_integer_literals, map_module, rewrite_infix_to_calls};
+
+    #[derive(Default)]
+    struct IntegerCollector {
+        integers: Vec<FieldElement>,
+        negative: Vec<bool>,
+    }
+
+    impl Visitor for IntegerCollector {
+        fn visit_literal_integer(&mut self, value: FieldElement, negative: bool) {
+            self.integers.push(value);
+            self.negative.push(negative);
+        }
+    }
+
+    fn collect_integers(module: &ParsedModule) -> Vec<FieldElement> {
+        collect_integers_and_signs(module).0
+    }
+
+    fn collect_integers_and_signs(module: &ParsedModule) -> (Vec<FieldElement>, Vec<bool>) {
+        let mut collector = IntegerCollector::default();
+        module.accept(&mut collector);
+        (collector.integers, collector.negative)
+    }
+
+    /// Increments every integer literal by one.
+    fn increment_integers(expression: Expression) -> Expression {
+        match expression.kind {
+            ExpressionKind::Literal(Literal::Integer(value, negative)) => {
+                let kind = ExpressionKind::Literal(Literal::Integer(
+                    value + FieldElement::one(),
+                    negative,
+                ));
+                Expression { kind, span: expression.span }
+            }
+            _ => expression,
+        }
+    }
+
+    #[test]
+    fn maps_expressions_in_every_position() {
+        let src = "
+        global G: Field = 1;
+
+        #[attr(1)]
+        fn main(x: Field) -> pub Field {
+            let mut a = [1, 1];
+            a[1] = -1;
+            for i in 1..1 {
+                assert(i == 1, \"msg\");
+            }
+            let f = |y: Field| y + 1;
+            if x == 1 { f(1) } else { (x, 1).0 }
+        }
+
+        trait Foo {
+            let N: u32 = 1;
+            fn foo() -> Field { 1 }
+        }
+
+        impl Foo for Field {
+            let N: u32 = 1;
+            fn foo() -> Field { 1 }
+        }
+
+        impl Field {
+            fn bar(self) -> Field { comptime { 1 } }
+        }
+
+        mod inner {
+            fn baz() -> Field { unsafe { [1; 1][0] } }
+        }
+        ";
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let integers = collect_integers(&module);
+        assert!(integers.iter().filter(|value| value.is_one()).count() >= 20);
+
+        let mapped = map_module(module, increment_integers);
+        let expected = vecmap(integers, |value| value + FieldElement::one());
+        assert_eq!(collect_integers(&mapped), expected);
+    }
+
+    #[test]
+    fn maps_integer_literals_in_place() {
+        let src = "
+        fn main(x: [Field; 3]) -> pub Field {
+            let a = [2; 3];
+            foo(x[1], a[0] + 4, true)
+        }
+
+        fn foo(a: Field, b: Field, _c: bool) -> Field { a + b }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let mut visited = 0;
+        map_integer_literals(&mut module, |literal| {
+            visited += 1;
+            if let Literal::Integer(value, negative) = literal {
+                *value = *value * FieldElement::from(10u128);
+                *negative = true;
+            }
+        });
+        assert_eq!(visited, 5);
+
+        let (integers, negative) = collect_integers_and_signs(&module);
+        let expected = vecmap([2u128, 3, 1, 0, 4], |value| FieldElement::from(value * 10));
+        assert_eq!(integers, expected);
+        assert!(negative.into_iter().all(|negative| negative));
+    }
+
+    #[test]
+    fn desugars_chained_method_calls_inside_out() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let y = x.foo(1).bar::<u32>(x.baz());
+            y
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        desugar_method_calls(&mut module);
+
+        let ItemKind::Function(function) = &module.items[0].kind else {
+            panic!("Expected a function");
+        };
+        let let_statement = &function.def.body.statements[0];
+        assert_eq!(let_statement.kind.to_string(), "let y = bar::<u32>(foo(x, 1), baz(x))");
+
+        let StatementKind::Let(let_statement) = &let_statement.kind else {
+            panic!("Expected a let statement");
+        };
+        assert_eq!(let_statement.expression.span, Span::default());
+    }
+
+    fn rewrite_multiplications(src: &str) -> Vec<String> {
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let op = Spanned::from(Span::default(), BinaryOpKind::Multiply);
+        let func_path = Path::from_ident(Ident::from("checked_mul"));
+        rewrite_infix_to_calls(&mut module, op, func_path);
+
+        let ItemKind::Function(function) = &module.items[0].kind else {
+            panic!("Expected a function");
+        };
+        vecmap(&function.def.body.statements, |statement| statement.kind.to_string())
+    }
+
+    #[test]
+    fn rewrites_only_the_targeted_operator() {
+        let body = rewrite_multiplications(
+            "fn main(a: Field, b: Field, c: Field) -> pub Field { let d = a + b * c - a * b; d / c }",
+        );
+        assert_eq!(body, vec!["let d = ((a + checked_mul(b, c)) - checked_mul(a, b))", "(d / c)"]);
+    }
+
+    #[test]
+    fn preserves_operand_evaluation_order() {
+        let body = rewrite_multiplications("fn main() -> pub Field { foo() * bar() * baz() }");
+        assert_eq!(body, vec!["checked_mul(checked_mul(foo(), bar()), baz())"]);
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    ast::{
+        scopes::{walk_module, ScopedRewriter},
+        Expression, ExpressionKind, Ident, UnresolvedType,
+    },
+    ParsedModule,
+};
+
+/// Renames every local variable of `module` named `prefix` or `{prefix}_N` (where `N` is a
+/// number) to a fresh `{prefix}_0`, `{prefix}_1`, ... so that temporaries introduced by repeated
+/// macro expansions no longer shadow each other. Other names starting with `prefix`, such as
+/// `{prefix}l` or `{prefix}_total`, are left as is.
+///
+/// Numbering restarts at zero in each function and follows the order the variables are declared
+/// in, skipping any name which is used in `module` to refer to something other than a local
+/// variable (such as a global) so that it isn't captured. Every use of a variable is renamed along
+/// with its declaration, following Noir's scoping rules. Names which don't refer to a local
+/// variable are left as is, as are variables interpolated in format strings.
+pub fn freshen_temporaries(module: &mut ParsedModule, prefix: &str) {
+    let reserved = reserved_names(module, prefix, false);
+    let mut freshener = Freshener { prefix, reserved, next_index: 0, scopes: Vec::new() };
+    walk_module(module, &mut freshener);
+}
+
+/// Returns whether `name` is `prefix` or `{prefix}_N` where `N` is a number.
+fn is_temporary(name: &str, prefix: &str) -> bool {
+    match name.strip_prefix(prefix) {
+        Some("") => true,
+        Some(suffix) => suffix.strip_prefix('_').map_or(false, |digits| {
+            !digits.is_empty() && digits.bytes().all(|byte| byte.is_ascii_digit())
+        }),
+        None => false,
+    }
+}
+
+/// Returns the names of the form `prefix` or `{prefix}_N` which `module` uses to refer to
+/// something other than a local variable, along with those of its local variables if
+/// `include_locals` is `true`. These can't be used as fresh names without capturing them.
+pub(super) fn reserved_names(
+    module: &mut ParsedModule,
+    prefix: &str,
+    include_locals: bool,
+) -> HashSet<String> {
+    let mut collector = ReservedNameCollector {
+        prefix,
+        include_locals,
+        scopes: Vec::new(),
+        reserved: HashSet::new(),
+    };
+    walk_module(module, &mut collector);
+    collector.reserved
+}
+
+/// Collects the temporary names which are used to refer to something other than a local
+/// variable, as these can't be used as fresh names.
+struct ReservedNameCollector<'a> {
+    prefix: &'a str,
+    /// Whether the names of local variables are reserved too.
+    include_locals: bool,
+    /// The temporaries declared in each enclosing scope.
+    scopes: Vec<HashSet<String>>,
+    reserved: HashSet<String>,
+}
+
+impl ReservedNameCollector<'_> {
+    fn visit_name(&mut self, ident: &Ident) {
+        let name = &ident.0.contents;
+        if is_temporary(name, self.prefix) && !self.scopes.iter().any(|scope| scope.contains(name))
+        {
+            self.reserved.insert(name.clone());
+        }
+    }
+}
+
+impl ScopedRewriter for ReservedNameCollector<'_> {
+    fn enter_scope(&mut self) {
+        self.scopes.push(HashSet::new());
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn visit_binding(&mut self, ident: &mut Ident, _: Option<&UnresolvedType>) {
+        if is_temporary(&ident.0.contents, self.prefix) {
+            let scope = self.scopes.last_mut().expect("Bindings are always declared in a scope");
+            scope.insert(ident.0.contents.clone());
+            if self.include_locals {
+                self.reserved.insert(ident.0.contents.clone());
+            }
+        }
+    }
+
+    fn visit_variable(&mut self, expression: &mut Expression) {
+        if let ExpressionKind::Variable(path) = &expression.kind {
+            if path.is_ident() {
+                self.visit_name(&path.segments[0].ident);
+            }
+        }
+    }
+
+    fn visit_assigned_variable(&mut self, ident: &mut Ident) {
+        self.visit_name(ident);
+    }
+}
+
+struct Freshener<'a> {
+    prefix: &'a str,
+    /// The names which must not be used as fresh names.
+    reserved: HashSet<String>,
+    next_index: usize,
+    /// For each enclosing scope, maps the original name of each temporary declared in it to its
+    /// fresh name.
+    scopes: Vec<HashMap<String, String>>,
+}
+
+impl Freshener<'_> {
+    fn rename(&self, ident: &mut Ident) {
+        let fresh_name =
+            self.scopes.iter().rev().find_map(|scope| scope.get(&ident.0.contents)).cloned();
+        if let Some(fresh_name) = fresh_name {
+            ident.0.contents = fresh_name;
+        }
+    }
+
+    fn fresh_name(&mut self) -> String {
+        loop {
+            let name = format!("{}_{}", self.prefix, self.next_index);
+            self.next_index += 1;
+            if !self.reserved.contains(&name) {
+                return name;
+            }
+        }
+    }
+}
+
+impl ScopedRewriter for Freshener<'_> {
+    fn enter_function(&mut self, _: bool, _: &[String]) {
+        self.next_index = 0;
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn visit_binding(&mut self, ident: &mut Ident, _: Option<&UnresolvedType>) {
+        if !is_temporary(&ident.0.contents, self.prefix) {
+            return;
+        }
+
+        let fresh_name = self.fresh_name();
+        let scope = self.scopes.last_mut().expect("Bindings are always declared in a scope");
+        let original_name = std::mem::replace(&mut ident.0.contents, fresh_name.clone());
+        scope.insert(original_name, fresh_name);
+    }
+
+    fn visit_variable(&mut self, expression: &mut Expression) {
+        if let ExpressionKind::Variable(path) = &mut expression.kind {
+            if path.is_ident() {
+                self.rename(&mut path.segments[0].ident);
+            }
+        }
+    }
+
+    fn visit_assigned_variable(&mut self, ident: &mut Ident) {
+        self.rename(ident);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+
+    use crate::{
+        parser::{parse_program, ItemKind},
+        ParsedModule,
+    };
+
+    use super::freshen_temporaries;
+
+    fn parse(src: &str) -> ParsedModule {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        module
+    }
+
+    fn function_bodies(module: &ParsedModule) -> Vec<Vec<String>> {
+        vecmap(&module.items, |item| match &item.kind {
+            ItemKind::Function(function) => {
+                vecmap(&function.def.body.statements, |statement| statement.kind.to_string())
+            }
+            _ => panic!("Expected a function"),
+        })
+    }
+
+    #[test]
+    fn renames_colliding_temporaries() {
+        // Two expansions of the same macro, each introducing a `tmp`
+        let mut module = parse(
+            "
+            fn main(x: Field) {
+                let tmp = x + 1;
+                assert(tmp != 0);
+                let mut tmp = x * 2;
+                tmp = tmp + 1;
+                { let tmp = 3; assert(tmp == 3); }
+                assert(tmp != 0);
+            }
+
+            fn other(tmp: Field) -> Field { tmp + not_tmp }
+            ",
+        );
+        freshen_temporaries(&mut module, "tmp");
+
+        assert_eq!(
+            function_bodies(&module),
+            vec![
+                vec![
+                    "let tmp_0 = (x + 1)",
+                    "assert((tmp_0 != 0))",
+                    "let mut tmp_1 = (x * 2)",
+                    "tmp_1 = (tmp_1 + 1)",
+                    "{\n    let tmp_2 = 3\n    assert((tmp_2 == 3))\n}",
+                    "assert((tmp_1 != 0))",
+                ],
+                vec!["(tmp_0 + not_tmp)"],
+            ]
+        );
+    }
+
+    #[test]
+    fn only_renames_exact_temporaries_without_capturing_globals() {
+        let mut module = parse(
+            "
+            global tmp_0: Field = 1;
+
+            fn main(x: Field) {
+                let tmp = x;
+                let tmpl = tmp;
+                let tmp_total = tmpl;
+                let tmp_7 = tmp_total;
+                assert(tmp_7 != tmp_0);
+            }
+            ",
+        );
+        freshen_temporaries(&mut module, "tmp");
+
+        let ItemKind::Function(function) = &module.items[1].kind else {
+            panic!("Expected a function");
+        };
+        let statements =
+            vecmap(&function.def.body.statements, |statement| statement.kind.to_string());
+        assert_eq!(
+            statements,
+            vec![
+                "let tmp_1 = x",
+                "let tmpl = tmp_1",
+                "let tmp_total = tmpl",
+                "let tmp_2 = tmp_total",
+                "assert((tmp_2 != tmp_0))",
+            ]
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        scopes::{walk_module, ScopedRewriter},
+        spans::clear_spans,
+        Expression, ExpressionKind, Ident, PathKind, UnresolvedType,
+    },
+    ParsedModule,
+};
+
+/// Replaces every reference to the global `name` in `module` with a clone of `value`.
+///
+/// A reference is a variable whose path ends in `name`, so both `FOO` and `consts::FOO` are
+/// replaced when `name` is `FOO`. A plain `FOO` is left as is wherever it refers to a local
+/// variable, parameter or `for` loop index of the same name instead. Every span of the inlined
+/// value, including those of its statements, identifiers, patterns and types, is reset to the
+/// default span so that nothing points into the global's definition.
+pub fn inline_global(module: &mut ParsedModule, name: &str, value: Expression) {
+    let value = clear_spans(value);
+    let mut substituter = VariableSubstituter::new(name, value, true);
+    walk_module(module, &mut substituter);
+}
+
+/// Replaces the variables named `name` with a clone of `value`, except where a local variable of
+/// the same name shadows them.
+pub(super) struct VariableSubstituter<'a> {
+    name: &'a str,
+    value: Expression,
+    /// Whether paths with several segments which end in `name`, such as `consts::FOO`, are
+    /// replaced too.
+    replace_qualified_paths: bool,
+    /// For each enclosing scope, whether it declares a local variable named `name`.
+    scopes: Vec<bool>,
+}
+
+impl<'a> VariableSubstituter<'a> {
+    pub(super) fn new(name: &'a str, value: Expression, replace_qualified_paths: bool) -> Self {
+        VariableSubstituter { name, value, replace_qualified_paths, scopes: Vec::new() }
+    }
+}
+
+impl ScopedRewriter for VariableSubstituter<'_> {
+    fn enter_scope(&mut self) {
+        self.scopes.push(false);
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn visit_binding(&mut self, ident: &mut Ident, _: Option<&UnresolvedType>) {
+        if ident.0.contents == self.name {
+            *self.scopes.last_mut().expect("Bindings are always declared in a scope") = true;
+        }
+    }
+
+    fn visit_variable(&mut self, expression: &mut Expression) {
+        let ExpressionKind::Variable(path) = &expression.kind else {
+            return;
+        };
+        if path.segments.last().map_or(true, |segment| segment.ident.0.contents != self.name) {
+            return;
+        }
+
+        let is_plain = path.kind == PathKind::Plain && path.segments.len() == 1;
+        let is_replaced = if is_plain {
+            !self.scopes.iter().any(|declares_name| *declares_name)
+        } else {
+            self.replace_qualified_paths
+        };
+        if is_replaced {
+            *expression = self.value.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+    use noirc_errors::Span;
+
+    use crate::{
+        ast::{Expression, ExpressionKind, StatementKind, UnresolvedTypeData},
+        parser::{parse_program, ItemKind},
+    };
+
+    use super::inline_global;
+
+    #[test]
+    fn inlines_only_references_to_the_global() {
+        let src = "
+        global FOO: Field = 3;
+
+        fn main(x: Field) -> Field {
+            let a = FOO + x;
+            let b = { let FOO = 1; FOO };
+            let c = |FOO: Field| FOO;
+            let d = consts::FOO + FOO;
+            let FOO = 2;
+            a + b + c(x) + d + FOO
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let value = Expression::new(ExpressionKind::integer(42_u128.into()), Default::default());
+        inline_global(&mut module, "FOO", value);
+
+        let ItemKind::Function(function) = &module.items[1].kind else {
+            panic!("Expected a function");
+        };
+        let body = vecmap(&function.def.body.statements, |statement| statement.kind.to_string());
+        assert_eq!(
+            body,
+            vec![
+                "let a = (42 + x)",
+                "let b = {\n    let FOO = 1\n    FOO\n}",
+                "let c = |FOO: Field| -> unspecified { FOO }",
+                "let d = (42 + 42)",
+                "let FOO = 2",
+                "((((a + b) + c(x)) + d) + FOO)",
+            ]
+        );
+    }
+
+    /// Returns the body of `fn main() -> Field { FOO }` once `FOO` is replaced by the value of
+    /// `global FOO: Field = value;`.
+    fn inlined_body(value: &str) -> Expression {
+        let (module, errors) = parse_program(&format!("global FOO: Field = {value};"));
+        assert!(errors.is_empty());
+        let ItemKind::Global(global, _) = &module.items[0].kind else {
+            panic!("Expected a global");
+        };
+        let value = global.expression.clone();
+
+        let (mut module, errors) = parse_program("fn main() -> Field { FOO }");
+        assert!(errors.is_empty());
+        inline_global(&mut module, "FOO", value);
+
+        let ItemKind::Function(function) = &mut module.items[0].kind else {
+            panic!("Expected a function");
+        };
+        let StatementKind::Expression(body) = function.def.body.statements.remove(0).kind else {
+            panic!("Expected an expression");
+        };
+        body
+    }
+
+    #[test]
+    fn clears_nested_spans() {
+        let body = inlined_body("BAR + (1 * 2)");
+        assert_eq!(body.to_string(), "(BAR + ((1 * 2)))");
+        assert_eq!(body.span, Span::default());
+
+        let ExpressionKind::Infix(infix) = &body.kind else {
+            panic!("Expected an infix expression");
+        };
+        assert_eq!(infix.lhs.span, Span::default());
+        assert_eq!(infix.rhs.span, Span::default());
+        let ExpressionKind::Variable(path) = &infix.lhs.kind else {
+            panic!("Expected a variable");
+        };
+        assert_eq!(path.span, Span::default());
+        let ExpressionKind::Parenthesized(product) = &infix.rhs.kind else {
+            panic!("Expected a parenthesized expression");
+        };
+        assert_eq!(product.span, Span::default());
+    }
+
+    #[test]
+    fn clears_spans_of_statements_and_types() {
+        let body = inlined_body("{ let a: u8 = s.f; a.g() as Field }");
+        let ExpressionKind::Block(block) = &body.kind else {
+            panic!("Expected a block");
+        };
+        let StatementKind::Let(let_statement) = &block.statements[0].kind else {
+            panic!("Expected a let statement");
+        };
+        assert_eq!(let_statement.pattern.span(), Span::default());
+        assert_eq!(let_statement.r#type.span, Span::default());
+        let ExpressionKind::MemberAccess(member_access) = &let_statement.expression.kind else {
+            panic!("Expected a member access");
+        };
+        assert_eq!(member_access.rhs.span(), Span::default());
+
+        let StatementKind::Expression(cast) = &block.statements[1].kind else {
+            panic!("Expected an expression");
+        };
+        let ExpressionKind::Cast(cast) = &cast.kind else {
+            panic!("Expected a cast");
+        };
+        assert_eq!(cast.r#type.span, Span::default());
+        let ExpressionKind::MethodCall(method_call) = &cast.lhs.kind else {
+            panic!("Expected a method call");
+        };
+        assert_eq!(method_call.method_name.span(), Span::default());
+
+        let body = inlined_body("|x: u8| Foo { y: x }");
+        let ExpressionKind::Lambda(lambda) = &body.kind else {
+            panic!("Expected a lambda");
+        };
+        let (pattern, typ) = &lambda.parameters[0];
+        assert_eq!(pattern.span(), Span::default());
+        assert_eq!(typ.span, Span::default());
+        let ExpressionKind::Constructor(constructor) = &lambda.body.kind else {
+            panic!("Expected a constructor");
+        };
+        assert_eq!(constructor.typ.span, Span::default());
+        let UnresolvedTypeData::Named(path, ..) = &constructor.typ.typ else {
+            panic!("Expected a named type");
+        };
+        assert_eq!(path.segments[0].ident.span(), Span::default());
+        assert_eq!(constructor.fields[0].0.span(), Span::default());
+    }
+}
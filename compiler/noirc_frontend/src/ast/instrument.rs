@@ -0,0 +1,205 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        BlockExpression, Expression, ExpressionKind, FunctionKind, Ident, NoirFunction, Path,
+        Pattern, Statement, StatementKind, TraitImplItemKind, TraitItem, UnresolvedTypeData,
+    },
+    parser::{Item, ItemKind},
+    ParsedModule,
+};
+
+/// The variable a function's tail expression is bound to while the epilogue runs. The `$` makes
+/// sure it can't clash with, or be referenced by, user code.
+const RESULT_NAME: &str = "$instrumented_result";
+
+/// Inserts `prologue` at the start and `epilogue` at the end of the body of every function in
+/// `module`, including methods, trait impl functions, default trait methods and functions in
+/// inline submodules. Builtin, low-level and oracle functions have no body so they are skipped.
+///
+/// A function's value is preserved: if its body ends in a tail expression, that expression is
+/// bound to a fresh variable before the epilogue runs and the variable is returned after it.
+/// Injected expression statements are turned into `expr;` statements so that they never become
+/// the value of the body. The spans of the injected statements are reset to the default span.
+pub fn instrument_functions(
+    module: &mut ParsedModule,
+    prologue: Vec<Statement>,
+    epilogue: Vec<Statement>,
+) {
+    let instrumenter =
+        Instrumenter { prologue: prepare_injected(prologue), epilogue: prepare_injected(epilogue) };
+    instrumenter.instrument_items(&mut module.items);
+}
+
+fn prepare_injected(statements: Vec<Statement>) -> Vec<Statement> {
+    statements
+        .into_iter()
+        .map(|statement| {
+            let kind = match statement.kind {
+                StatementKind::Expression(expression) => StatementKind::Semi(expression),
+                kind => kind,
+            };
+            Statement { kind, span: Span::default() }
+        })
+        .collect()
+}
+
+struct Instrumenter {
+    prologue: Vec<Statement>,
+    epilogue: Vec<Statement>,
+}
+
+impl Instrumenter {
+    fn instrument_items(&self, items: &mut [Item]) {
+        for item in items {
+            match &mut item.kind {
+                ItemKind::Function(function) => self.instrument_function(function),
+                ItemKind::Impl(type_impl) => {
+                    for (method, _) in &mut type_impl.methods {
+                        self.instrument_function(&mut method.item);
+                    }
+                }
+                ItemKind::TraitImpl(trait_impl) => {
+                    for item in &mut trait_impl.items {
+                        if let TraitImplItemKind::Function(function) = &mut item.item.kind {
+                            self.instrument_function(function);
+                        }
+                    }
+                }
+                ItemKind::Trait(noir_trait) => {
+                    for item in &mut noir_trait.items {
+                        if let TraitItem::Function { body: Some(body), .. } = &mut item.item {
+                            self.instrument_body(body);
+                        }
+                    }
+                }
+                ItemKind::Submodules(submodule) => {
+                    self.instrument_items(&mut submodule.contents.items);
+                }
+                ItemKind::Import(..)
+                | ItemKind::Struct(_)
+                | ItemKind::TypeAlias(_)
+                | ItemKind::Global(..)
+                | ItemKind::ModuleDecl(_)
+                | ItemKind::InnerAttribute(_) => (),
+            }
+        }
+    }
+
+    fn instrument_function(&self, function: &mut NoirFunction) {
+        if function.kind == FunctionKind::Normal {
+            self.instrument_body(&mut function.def.body);
+        }
+    }
+
+    fn instrument_body(&self, body: &mut BlockExpression) {
+        let mut statements = std::mem::take(&mut body.statements);
+        let tail = match statements.pop() {
+            Some(Statement { kind: StatementKind::Expression(expression), .. })
+                if !self.epilogue.is_empty() =>
+            {
+                Some(expression)
+            }
+            Some(statement) => {
+                statements.push(statement);
+                None
+            }
+            None => None,
+        };
+
+        body.statements = self.prologue.clone();
+        body.statements.extend(statements);
+
+        let Some(tail) = tail else {
+            body.statements.extend(self.epilogue.iter().cloned());
+            return;
+        };
+
+        // let $instrumented_result = <tail>;
+        let result = Ident::from(RESULT_NAME);
+        let let_result = StatementKind::new_let(
+            Pattern::Identifier(result.clone()),
+            UnresolvedTypeData::Unspecified.with_span(Span::default()),
+            tail,
+            Vec::new(),
+        );
+        body.statements.push(Statement { kind: let_result, span: Span::default() });
+        body.statements.extend(self.epilogue.iter().cloned());
+
+        // $instrumented_result
+        let result =
+            Expression::new(ExpressionKind::Variable(Path::from_ident(result)), Span::default());
+        body.statements
+            .push(Statement { kind: StatementKind::Expression(result), span: Span::default() });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+
+    use crate::{
+        ast::{Statement, StatementKind},
+        parser::{parse_program, ItemKind},
+        ParsedModule,
+    };
+
+    use super::instrument_functions;
+
+    fn parse(src: &str) -> ParsedModule {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        module
+    }
+
+    /// Returns the body of the first function in `module`.
+    fn body_of_first_function(module: &ParsedModule) -> &[Statement] {
+        match &module.items[0].kind {
+            ItemKind::Function(function) => &function.def.body.statements,
+            _ => panic!("Expected a function"),
+        }
+    }
+
+    fn body_strings(module: &ParsedModule) -> Vec<String> {
+        vecmap(body_of_first_function(module), |statement| statement.kind.to_string())
+    }
+
+    #[test]
+    fn instruments_functions_preserving_their_value() {
+        let snippets = parse("fn snippets() { enter(1); exit(2) }");
+        let snippets = body_of_first_function(&snippets);
+
+        let mut module = parse(
+            "
+            fn returns_value() -> Field { let x = 1; x + 1 }
+            fn returns_unit() { assert(true); }
+            impl Foo { fn method(self) {} }
+            ",
+        );
+        instrument_functions(&mut module, vec![snippets[0].clone()], vec![snippets[1].clone()]);
+
+        assert_eq!(
+            body_strings(&module),
+            vec![
+                "enter(1);",
+                "let x = 1",
+                "let $instrumented_result = (x + 1)",
+                "exit(2);",
+                "$instrumented_result",
+            ]
+        );
+
+        module.items.remove(0);
+        assert_eq!(body_strings(&module), vec!["enter(1);", "assert(true)", "exit(2);"]);
+
+        let ItemKind::Impl(type_impl) = &module.items[1].kind else {
+            panic!("Expected an impl");
+        };
+        let method_body = &type_impl.methods[0].0.item.def.body.statements;
+        assert!(matches!(
+            method_body.last().map(|statement| &statement.kind),
+            Some(StatementKind::Semi(_))
+        ));
+        assert_eq!(method_body.len(), 2);
+    }
+}
@@ -0,0 +1,441 @@
+use std::collections::{HashMap, HashSet};
+
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        analysis::names_used_as_generics,
+        freshen::reserved_names,
+        scopes::{walk_module, ScopedRewriter},
+        BlockExpression, CallExpression, Expression, ExpressionKind, FunctionDefinition,
+        FunctionReturnType, Ident, ItemVisibility, Lambda, NoirFunction, Param, Path, Pattern,
+        Statement, StatementKind, UnresolvedType, UnresolvedTypeData, Visibility,
+    },
+    parser::ItemKind,
+    token::Attributes,
+    ParsedModule,
+};
+
+/// Replaces the lambdas of `module` with references to new top-level functions, returning the
+/// functions so that they can be appended to the module.
+///
+/// The lifted functions are named `__lambda_0`, `__lambda_1`, ..., skipping any name already
+/// declared or used in `module` (such as the name of a function lifted by a previous call), and
+/// take the lambda's parameters followed by one parameter per variable the lambda captures, in the
+/// order they're first used. To lift the lambdas of several modules into one, merge them before
+/// lifting.
+/// A lambda without captures is replaced by a path to its function. A lambda with captures is
+/// replaced by a lambda which only forwards its parameters and captures to its function, as a
+/// function reference alone can't carry them.
+///
+/// As types aren't known before elaboration, a lambda is only lifted if its parameter and return
+/// types are written in the source and so are the types of the variables it captures (e.g. a
+/// function parameter or a `let` with a type annotation). A lambda whose types or body mention a
+/// generic of the enclosing function, impl or trait, or `Self`, is left as is too, as these
+/// aren't in scope in a top-level function. Lifted functions are unconstrained if the function
+/// the lambda is declared in is. Generated nodes get the default span.
+pub fn lift_lambdas(module: &mut ParsedModule) -> Vec<NoirFunction> {
+    let mut taken_names = reserved_names(module, LAMBDA_PREFIX, true);
+    taken_names.extend(module.items.iter().filter_map(|item| match &item.kind {
+        ItemKind::Function(function) => Some(function.name().to_string()),
+        _ => None,
+    }));
+    let taken_arguments = reserved_names(module, ARGUMENT_PREFIX, true);
+
+    let mut lifter = LambdaLifter { taken_names, taken_arguments, ..Default::default() };
+    walk_module(module, &mut lifter);
+    lifter.lifted
+}
+
+/// The prefix of the names of lifted functions.
+const LAMBDA_PREFIX: &str = "__lambda";
+
+/// The prefix of the names of the parameters of the lambdas forwarding to lifted functions.
+const ARGUMENT_PREFIX: &str = "__arg";
+
+#[derive(Default)]
+struct LambdaLifter {
+    is_unconstrained: bool,
+    /// The names of the generics in scope in the function being walked, including `Self`.
+    generics: Vec<String>,
+    /// The names of the module's functions, and the names of the form `__lambda_N` used in the
+    /// module, which lifted functions can't be named.
+    taken_names: HashSet<String>,
+    /// The names of the form `__arg_N` used in the module, which forwarding lambdas can't use.
+    taken_arguments: HashSet<String>,
+    next_index: usize,
+    /// For each enclosing scope, maps the name of each variable declared in it to its type, if it
+    /// was written in the source.
+    scopes: Vec<HashMap<String, Option<UnresolvedType>>>,
+    /// The lambdas enclosing the expression currently being walked, innermost last.
+    lambdas: Vec<LambdaContext>,
+    lifted: Vec<NoirFunction>,
+}
+
+struct LambdaContext {
+    /// The number of scopes which were entered when the lambda was reached. Variables declared in
+    /// scopes below this depth are declared outside of the lambda.
+    scope_depth: usize,
+    captures: Vec<(String, Option<UnresolvedType>)>,
+}
+
+impl LambdaLifter {
+    fn fresh_name(&mut self) -> String {
+        loop {
+            let name = format!("{LAMBDA_PREFIX}_{}", self.next_index);
+            self.next_index += 1;
+            if !self.taken_names.contains(&name) {
+                return name;
+            }
+        }
+    }
+}
+
+impl ScopedRewriter for LambdaLifter {
+    fn enter_function(&mut self, is_unconstrained: bool, generics: &[String]) {
+        self.is_unconstrained = is_unconstrained;
+        self.generics = generics.to_vec();
+    }
+
+    fn enter_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+    }
+
+    fn exit_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    fn visit_binding(&mut self, ident: &mut Ident, typ: Option<&UnresolvedType>) {
+        let scope = self.scopes.last_mut().expect("Bindings are always declared in a scope");
+        scope.insert(ident.0.contents.clone(), typ.cloned());
+    }
+
+    fn visit_variable(&mut self, expression: &mut Expression) {
+        let ExpressionKind::Variable(path) = &expression.kind else {
+            return;
+        };
+        let Some(ident) = path.as_ident() else {
+            return;
+        };
+
+        let name = &ident.0.contents;
+        let Some(depth) = self.scopes.iter().rposition(|scope| scope.contains_key(name)) else {
+            return;
+        };
+
+        for lambda in &mut self.lambdas {
+            let is_captured = lambda.scope_depth > depth;
+            if is_captured && lambda.captures.iter().all(|(capture, _)| capture != name) {
+                lambda.captures.push((name.clone(), self.scopes[depth][name].clone()));
+            }
+        }
+    }
+
+    fn enter_lambda(&mut self) {
+        self.lambdas.push(LambdaContext { scope_depth: self.scopes.len(), captures: Vec::new() });
+    }
+
+    fn exit_lambda(&mut self, expression: &mut Expression) {
+        let context = self.lambdas.pop().expect("Lambdas are entered before being exited");
+        let ExpressionKind::Lambda(lambda) = &expression.kind else {
+            return;
+        };
+
+        let is_typed = |typ: &UnresolvedType| !matches!(typ.typ, UnresolvedTypeData::Unspecified);
+        let captures: Option<Vec<_>> = context
+            .captures
+            .into_iter()
+            .map(|(name, typ)| typ.map(|typ| (Ident::from(name), typ)))
+            .collect();
+        let Some(captures) = captures else {
+            return;
+        };
+        if !lambda.parameters.iter().all(|(_, typ)| is_typed(typ)) || !is_typed(&lambda.return_type)
+        {
+            return;
+        }
+
+        let capture_types: Vec<_> = captures.iter().map(|(_, typ)| typ).collect();
+        let used_names = names_used_as_generics(expression, &capture_types);
+        let uses_generics =
+            used_names.iter().any(|name| name == "Self" || self.generics.contains(name));
+        if uses_generics {
+            return;
+        }
+
+        let ExpressionKind::Lambda(lambda) =
+            std::mem::replace(&mut expression.kind, ExpressionKind::Error)
+        else {
+            unreachable!("Checked to be a lambda above");
+        };
+        let name = Ident::from(self.fresh_name());
+        *expression = lambda_replacement(&name, &lambda, &captures, &self.taken_arguments);
+        self.lifted.push(lift_lambda(name, *lambda, captures, self.is_unconstrained));
+    }
+}
+
+fn lift_lambda(
+    name: Ident,
+    lambda: Lambda,
+    captures: Vec<(Ident, UnresolvedType)>,
+    is_unconstrained: bool,
+) -> NoirFunction {
+    let captures = captures.into_iter().map(|(name, typ)| (Pattern::Identifier(name), typ));
+    let parameters = lambda
+        .parameters
+        .into_iter()
+        .chain(captures)
+        .map(|(pattern, typ)| Param {
+            visibility: Visibility::Private,
+            pattern,
+            typ,
+            span: Span::default(),
+        })
+        .collect();
+
+    let body = match lambda.body.kind {
+        ExpressionKind::Block(block) => block,
+        kind => {
+            let span = lambda.body.span;
+            let statement =
+                Statement { kind: StatementKind::Expression(Expression { kind, span }), span };
+            BlockExpression { statements: vec![statement] }
+        }
+    };
+
+    NoirFunction::normal(FunctionDefinition {
+        name,
+        attributes: Attributes::empty(),
+        is_unconstrained,
+        is_comptime: false,
+        visibility: ItemVisibility::Private,
+        generics: Vec::new(),
+        parameters,
+        body,
+        span: Span::default(),
+        where_clause: Vec::new(),
+        return_type: FunctionReturnType::Ty(lambda.return_type),
+        return_visibility: Visibility::Private,
+    })
+}
+
+/// Returns the expression replacing `lambda` once it's lifted to the function `name`: either
+/// `name` itself or, if it has captures, `|__arg_0: T0, ...| -> R name(__arg_0, ..., captures...)`
+/// where the parameter names skip those in `taken_arguments`.
+fn lambda_replacement(
+    name: &Ident,
+    lambda: &Lambda,
+    captures: &[(Ident, UnresolvedType)],
+    taken_arguments: &HashSet<String>,
+) -> Expression {
+    let variable = |name: &Ident| {
+        Expression::new(ExpressionKind::Variable(Path::from_ident(name.clone())), Span::default())
+    };
+
+    let function = variable(name);
+    if captures.is_empty() {
+        return function;
+    }
+
+    let argument_names = (0..)
+        .map(|index| format!("{ARGUMENT_PREFIX}_{index}"))
+        .filter(|name| !taken_arguments.contains(name));
+    let parameters: Vec<_> = lambda
+        .parameters
+        .iter()
+        .zip(argument_names)
+        .map(|((_, typ), name)| (Ident::from(name), typ.clone()))
+        .collect();
+    let arguments = parameters
+        .iter()
+        .map(|(name, _)| name)
+        .chain(captures.iter().map(|(name, _)| name))
+        .map(variable)
+        .collect();
+    let call = CallExpression { func: Box::new(function), arguments, is_macro_call: false };
+
+    let forwarding_lambda = Lambda {
+        parameters: parameters
+            .into_iter()
+            .map(|(name, typ)| (Pattern::Identifier(name), typ))
+            .collect(),
+        return_type: lambda.return_type.clone(),
+        body: Expression::new(ExpressionKind::Call(Box::new(call)), Span::default()),
+    };
+    Expression::new(ExpressionKind::Lambda(Box::new(forwarding_lambda)), Span::default())
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+
+    use crate::parser::{parse_program, Item, ItemKind};
+
+    use super::lift_lambdas;
+
+    #[test]
+    fn lifts_simple_and_capturing_lambdas() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let double = |a: Field| -> Field { a * 2 };
+            let add_x = |a: Field| -> Field { a + x };
+            let untyped = |a| a;
+            double(add_x(untyped(x)))
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let lifted = lift_lambdas(&mut module);
+
+        let ItemKind::Function(main) = &module.items[0].kind else {
+            panic!("Expected a function");
+        };
+        let body = vecmap(&main.def.body.statements, |statement| statement.kind.to_string());
+        assert_eq!(
+            body,
+            vec![
+                "let double = __lambda_0",
+                "let add_x = |__arg_0: Field| -> Field { __lambda_1(__arg_0, x) }",
+                "let untyped = |a: unspecified| -> unspecified { a }",
+                "double(add_x(untyped(x)))",
+            ]
+        );
+
+        let names = vecmap(&lifted, |function| function.name().to_string());
+        assert_eq!(names, vec!["__lambda_0", "__lambda_1"]);
+
+        let parameters =
+            vecmap(&lifted[1].def.parameters, |param| format!("{}: {}", param.pattern, param.typ));
+        assert_eq!(parameters, vec!["a: Field", "x: Field"]);
+        assert_eq!(lifted[1].def.return_type.to_string(), "Field");
+        assert_eq!(lifted[1].def.body.statements[0].kind.to_string(), "(a + x)");
+    }
+
+    #[test]
+    fn does_not_lift_lambdas_capturing_untyped_variables() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let y = x + 1;
+            let add_y = |a: Field| -> Field { a + y };
+            add_y(x)
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        assert!(lift_lambdas(&mut module).is_empty());
+    }
+
+    #[test]
+    fn does_not_lift_lambdas_using_generics_or_self() {
+        let src = "
+        fn foo<T>(x: T) {
+            let f = |y: T| y;
+            let g = |y: T| -> T { y };
+            let h = |y: Field| -> Field { let _z: [T; 1] = [x]; y };
+            let i = |y: Field| -> Field { y + 1 };
+        }
+
+        impl<U> Bar<U> {
+            fn bar(self) -> Field {
+                let f = |y: Field| -> Field { self.baz(y) };
+                let g = |y: U| -> U { y };
+                let h = |y: Field| -> Field { Self::qux(y) };
+                0
+            }
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let lifted = lift_lambdas(&mut module);
+        let names = vecmap(&lifted, |function| function.name().to_string());
+        assert_eq!(names, vec!["__lambda_0"]);
+        assert_eq!(lifted[0].def.body.statements[0].kind.to_string(), "(y + 1)");
+        assert!(lifted[0].def.generics.is_empty());
+    }
+
+    #[test]
+    fn does_not_reuse_names_of_lifted_functions() {
+        let src = "
+        fn main(x: Field) -> pub Field {
+            let double = |a: Field| -> Field { a * 2 };
+            double(x)
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let lifted = lift_lambdas(&mut module);
+        module.items.extend(lifted.into_iter().map(|function| Item {
+            kind: ItemKind::Function(function),
+            span: Default::default(),
+            doc_comments: Vec::new(),
+        }));
+
+        let (other, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        module.items.extend(other.items);
+
+        let lifted = lift_lambdas(&mut module);
+        let names = vecmap(&lifted, |function| function.name().to_string());
+        assert_eq!(names, vec!["__lambda_1"]);
+    }
+
+    #[test]
+    fn lifted_code_round_trips_through_the_parser() {
+        let src = "
+        global __lambda_0: Field = 1;
+
+        fn main(x: Field) -> pub Field {
+            let __arg_0: Field = x;
+            let double = |a: Field| -> Field { a * 2 };
+            let add = |a: Field| -> Field { a + __arg_0 + __lambda_0 };
+            double(add(x))
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        let lifted = lift_lambdas(&mut module);
+
+        let ItemKind::Function(main) = &module.items[1].kind else {
+            panic!("Expected a function");
+        };
+        let body = vecmap(&main.def.body.statements, |statement| statement.kind.to_string());
+        assert_eq!(
+            body,
+            vec![
+                "let __arg_0: Field = x",
+                "let double = __lambda_1",
+                "let add = |__arg_1: Field| -> Field { __lambda_2(__arg_1, __arg_0) }",
+                "double(add(x))",
+            ]
+        );
+
+        // Re-emit the rewritten function and the lifted ones as source, and parse them back
+        let mut output = format!("{} {{\n{}\n}}\n", main.def.signature(), body.join(";\n"));
+        for function in &lifted {
+            output.push_str(&format!("{} {}\n", function.def.signature(), function.def.body));
+        }
+        let (reparsed, errors) = parse_program(&output);
+        assert!(errors.is_empty(), "{output}");
+
+        let functions: Vec<_> = reparsed
+            .items
+            .iter()
+            .map(|item| match &item.kind {
+                ItemKind::Function(function) => function,
+                _ => panic!("Expected a function"),
+            })
+            .collect();
+        let names = vecmap(&functions, |function| function.name().to_string());
+        assert_eq!(names, vec!["main", "__lambda_1", "__lambda_2"]);
+
+        let reparsed_body =
+            vecmap(&functions[0].def.body.statements, |statement| statement.kind.to_string());
+        assert_eq!(reparsed_body, body);
+        assert_eq!(functions[2].def.signature(), lifted[1].def.signature());
+    }
+}
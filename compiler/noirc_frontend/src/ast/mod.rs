@@ -4,20 +4,47 @@
 //!
 //! Noir's Ast is produced by the parser and taken as input to name resolution,
 //! where it is converted into the Hir (defined in the hir_def module).
+pub mod analysis;
+mod annotate_lets;
 mod docs;
 mod expression;
+mod flatten;
+mod fold;
+mod freshen;
 mod function;
+mod inline_global;
+mod instrument;
+mod lift_lambdas;
+mod rename;
+mod scopes;
+mod spans;
+mod specialize;
 mod statement;
+mod strip;
 mod structure;
 mod traits;
+mod tupleize;
 mod type_alias;
+mod unroll;
 mod visitor;
 
 pub use visitor::AttributeTarget;
 pub use visitor::Visitor;
 
+pub use annotate_lets::annotate_lets;
 pub use expression::*;
+pub use flatten::flatten_blocks;
+pub use fold::{desugar_method_calls, map_integer_literals, map_module, rewrite_infix_to_calls};
+pub use freshen::freshen_temporaries;
 pub use function::*;
+pub use inline_global::inline_global;
+pub use instrument::instrument_functions;
+pub use lift_lambdas::lift_lambdas;
+pub use rename::rename_struct;
+pub use specialize::specialize_function;
+pub use strip::strip_constraints;
+pub use tupleize::tupleize_to_struct;
+pub use unroll::unroll_constant_for_loops;
 
 #[cfg(test)]
 use proptest_derive::Arbitrary;
@@ -0,0 +1,436 @@
+use std::collections::HashSet;
+
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        scopes::{walk_module, ScopedRewriter},
+        AsTraitPath, Expression, ExpressionKind, FunctionDefinition, FunctionReturnType,
+        GenericTypeArgs, Ident, LetStatement, Path, PathKind, PathSegment, Pattern,
+        TraitImplItemKind, TraitItem, UnresolvedTraitConstraint, UnresolvedType,
+        UnresolvedTypeData, UseTree, UseTreeKind,
+    },
+    parser::{Item, ItemKind},
+    ParsedModule,
+};
+
+/// Renames every struct named `from` declared in `module` (including in inline submodules) to
+/// `to`, along with every reference to it.
+///
+/// References are found in type positions (including constructors, casts and turbofishes), in
+/// struct patterns, in imports, and before the item name in paths such as `Point::new()` (when
+/// renaming `Point`). As names aren't resolved, a path refers to the struct if it's a plain or
+/// `crate::` path in which every segment before the struct's name is `self` or the name of an
+/// inline submodule, so both `Point` and `inner::Point` are renamed while `dep::foo::Point`,
+/// `super::Point` and `other::Point` (where `other` isn't declared in `module`) are left as is,
+/// including in imports. A variable which happens to be named `Point` doesn't refer to the struct
+/// so it's left as is. Each renamed identifier and path segment gets the default span.
+///
+/// Nothing is renamed if `module` doesn't declare a struct named `from`.
+pub fn rename_struct(module: &mut ParsedModule, from: &str, to: &str) {
+    if !declares_struct(&module.items, from) {
+        return;
+    }
+
+    let mut renamer = StructRenamer { from, to, submodules: submodule_names(&module.items) };
+    renamer.rename_items(&mut module.items);
+    walk_module(module, &mut renamer);
+}
+
+fn declares_struct(items: &[Item], name: &str) -> bool {
+    items.iter().any(|item| match &item.kind {
+        ItemKind::Struct(noir_struct) => noir_struct.name.0.contents == name,
+        ItemKind::Submodules(submodule) => declares_struct(&submodule.contents.items, name),
+        _ => false,
+    })
+}
+
+/// Returns the names of the inline submodules of `items`, at any depth.
+fn submodule_names(items: &[Item]) -> HashSet<String> {
+    let mut names = HashSet::new();
+    for item in items {
+        if let ItemKind::Submodules(submodule) = &item.kind {
+            names.insert(submodule.name.0.contents.clone());
+            names.extend(submodule_names(&submodule.contents.items));
+        }
+    }
+    names
+}
+
+struct StructRenamer<'a> {
+    from: &'a str,
+    to: &'a str,
+    /// The names of the inline submodules of the module, which may lead to the struct in a path.
+    submodules: HashSet<String>,
+}
+
+impl StructRenamer<'_> {
+    fn rename_items(&self, items: &mut [Item]) {
+        for item in items {
+            match &mut item.kind {
+                ItemKind::Function(function) => self.rename_signature(&mut function.def),
+                ItemKind::Struct(noir_struct) => {
+                    self.rename_ident(&mut noir_struct.name);
+                    for field in &mut noir_struct.fields {
+                        self.rename_type(&mut field.item.typ);
+                    }
+                }
+                ItemKind::Trait(noir_trait) => {
+                    self.rename_constraints(&mut noir_trait.where_clause);
+                    for item in &mut noir_trait.items {
+                        match &mut item.item {
+                            TraitItem::Function {
+                                parameters, return_type, where_clause, ..
+                            } => {
+                                for (_, typ) in parameters {
+                                    self.rename_type(typ);
+                                }
+                                self.rename_return_type(return_type);
+                                self.rename_constraints(where_clause);
+                            }
+                            TraitItem::Constant { typ, .. } => self.rename_type(typ),
+                            TraitItem::Type { .. } => (),
+                        }
+                    }
+                }
+                ItemKind::TraitImpl(trait_impl) => {
+                    self.rename_generic_type_args(&mut trait_impl.trait_generics);
+                    self.rename_type(&mut trait_impl.object_type);
+                    self.rename_constraints(&mut trait_impl.where_clause);
+                    for item in &mut trait_impl.items {
+                        match &mut item.item.kind {
+                            TraitImplItemKind::Function(function) => {
+                                self.rename_signature(&mut function.def);
+                            }
+                            TraitImplItemKind::Constant(_, typ, _)
+                            | TraitImplItemKind::Type { alias: typ, .. } => self.rename_type(typ),
+                        }
+                    }
+                }
+                ItemKind::Impl(type_impl) => {
+                    self.rename_type(&mut type_impl.object_type);
+                    self.rename_constraints(&mut type_impl.where_clause);
+                    for (method, _) in &mut type_impl.methods {
+                        self.rename_signature(&mut method.item.def);
+                    }
+                }
+                ItemKind::TypeAlias(type_alias) => self.rename_type(&mut type_alias.typ),
+                ItemKind::Import(use_tree, _) => {
+                    if self.is_local_prefix(use_tree.prefix.kind, &use_tree.prefix.segments) {
+                        self.rename_use_tree(use_tree);
+                    }
+                }
+                ItemKind::Submodules(submodule) => self.rename_items(&mut submodule.contents.items),
+                // Globals are renamed along with the function bodies
+                ItemKind::Global(..) | ItemKind::ModuleDecl(_) | ItemKind::InnerAttribute(_) => (),
+            }
+        }
+    }
+
+    fn rename_signature(&self, def: &mut FunctionDefinition) {
+        for parameter in &mut def.parameters {
+            self.rename_pattern(&mut parameter.pattern);
+            self.rename_type(&mut parameter.typ);
+        }
+        self.rename_return_type(&mut def.return_type);
+        self.rename_constraints(&mut def.where_clause);
+    }
+
+    fn rename_return_type(&self, return_type: &mut FunctionReturnType) {
+        if let FunctionReturnType::Ty(typ) = return_type {
+            self.rename_type(typ);
+        }
+    }
+
+    fn rename_constraints(&self, constraints: &mut [UnresolvedTraitConstraint]) {
+        for constraint in constraints {
+            self.rename_type(&mut constraint.typ);
+            self.rename_generic_type_args(&mut constraint.trait_bound.trait_generics);
+        }
+    }
+
+    fn rename_ident(&self, ident: &mut Ident) {
+        if ident.0.contents == self.from {
+            *ident = Ident::from(self.to);
+        }
+    }
+
+    /// Returns whether a path of the given kind starting with `segments` stays within the module.
+    fn is_local_prefix(&self, kind: PathKind, segments: &[PathSegment]) -> bool {
+        let is_local_segment = |segment: &PathSegment| {
+            let name = &segment.ident.0.contents;
+            name == "self" || self.submodules.contains(name)
+        };
+        matches!(kind, PathKind::Plain | PathKind::Crate) && segments.iter().all(is_local_segment)
+    }
+
+    /// Renames the segment at `index` of `path` if it's the struct's name and the segments before
+    /// it lead to the struct.
+    fn rename_segment(&self, path: &mut Path, index: usize) {
+        if !self.is_local_prefix(path.kind, &path.segments[..index]) {
+            return;
+        }
+        let Some(segment) = path.segments.get_mut(index) else {
+            return;
+        };
+        if segment.ident.0.contents == self.from {
+            segment.ident = Ident::from(self.to);
+            segment.span = Span::default();
+        }
+    }
+
+    /// Renames the type generics of each segment of `path`, and the last segment if `path` refers
+    /// to the struct.
+    fn rename_type_path(&self, path: &mut Path) {
+        self.rename_path_generics(path);
+        if let Some(last) = path.segments.len().checked_sub(1) {
+            self.rename_segment(path, last);
+        }
+    }
+
+    fn rename_path_generics(&self, path: &mut Path) {
+        for segment in &mut path.segments {
+            for typ in segment.generics.iter_mut().flatten() {
+                self.rename_type(typ);
+            }
+        }
+    }
+
+    fn rename_type(&self, typ: &mut UnresolvedType) {
+        match &mut typ.typ {
+            UnresolvedTypeData::Named(path, generics, _) => {
+                self.rename_type_path(path);
+                self.rename_generic_type_args(generics);
+            }
+            UnresolvedTypeData::TraitAsType(path, generics) => {
+                self.rename_path_generics(path);
+                self.rename_generic_type_args(generics);
+            }
+            UnresolvedTypeData::Array(_, element)
+            | UnresolvedTypeData::FormatString(_, element)
+            | UnresolvedTypeData::Slice(element)
+            | UnresolvedTypeData::Parenthesized(element)
+            | UnresolvedTypeData::MutableReference(element) => self.rename_type(element),
+            UnresolvedTypeData::Tuple(elements) => {
+                for element in elements {
+                    self.rename_type(element);
+                }
+            }
+            UnresolvedTypeData::Function(arguments, return_type, environment, _) => {
+                for argument in arguments {
+                    self.rename_type(argument);
+                }
+                self.rename_type(return_type);
+                self.rename_type(environment);
+            }
+            UnresolvedTypeData::AsTraitPath(as_trait_path) => {
+                self.rename_as_trait_path(as_trait_path);
+            }
+            UnresolvedTypeData::FieldElement
+            | UnresolvedTypeData::Integer(..)
+            | UnresolvedTypeData::Bool
+            | UnresolvedTypeData::Expression(_)
+            | UnresolvedTypeData::String(_)
+            | UnresolvedTypeData::Unit
+            | UnresolvedTypeData::Quoted(_)
+            | UnresolvedTypeData::Resolved(_)
+            | UnresolvedTypeData::Interned(_)
+            | UnresolvedTypeData::Unspecified
+            | UnresolvedTypeData::Error => (),
+        }
+    }
+
+    fn rename_generic_type_args(&self, generics: &mut GenericTypeArgs) {
+        for typ in &mut generics.ordered_args {
+            self.rename_type(typ);
+        }
+        for (_, typ) in &mut generics.named_args {
+            self.rename_type(typ);
+        }
+    }
+
+    fn rename_as_trait_path(&self, as_trait_path: &mut AsTraitPath) {
+        self.rename_type(&mut as_trait_path.typ);
+        self.rename_generic_type_args(&mut as_trait_path.trait_generics);
+    }
+
+    fn rename_pattern(&self, pattern: &mut Pattern) {
+        match pattern {
+            Pattern::Identifier(_) | Pattern::Interned(..) => (),
+            Pattern::Mutable(pattern, ..) => self.rename_pattern(pattern),
+            Pattern::Tuple(patterns, _) => {
+                for pattern in patterns {
+                    self.rename_pattern(pattern);
+                }
+            }
+            Pattern::Struct(path, fields, _) => {
+                self.rename_type_path(path);
+                for (_, pattern) in fields {
+                    self.rename_pattern(pattern);
+                }
+            }
+        }
+    }
+
+    /// Renames the leaves of `use_tree` which import the struct. The prefix of `use_tree` is
+    /// expected to stay within the module.
+    fn rename_use_tree(&self, use_tree: &mut UseTree) {
+        match &mut use_tree.kind {
+            UseTreeKind::Path(name, _) => self.rename_ident(name),
+            UseTreeKind::List(use_trees) => {
+                for use_tree in use_trees {
+                    if self.is_local_prefix(PathKind::Plain, &use_tree.prefix.segments) {
+                        self.rename_use_tree(use_tree);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl ScopedRewriter for StructRenamer<'_> {
+    fn exit_let(&mut self, let_statement: &mut LetStatement) {
+        self.rename_pattern(&mut let_statement.pattern);
+        self.rename_type(&mut let_statement.r#type);
+    }
+
+    fn exit_expression(&mut self, expression: &mut Expression) {
+        match &mut expression.kind {
+            ExpressionKind::Variable(path) => {
+                // In `from::new()` the struct is the segment before the item's name
+                self.rename_path_generics(path);
+                if let Some(type_segment) = path.segments.len().checked_sub(2) {
+                    self.rename_segment(path, type_segment);
+                }
+            }
+            ExpressionKind::Constructor(constructor) => self.rename_type(&mut constructor.typ),
+            ExpressionKind::Cast(cast) => self.rename_type(&mut cast.r#type),
+            ExpressionKind::MethodCall(method_call) => {
+                for typ in method_call.generics.iter_mut().flatten() {
+                    self.rename_type(typ);
+                }
+            }
+            ExpressionKind::Lambda(lambda) => {
+                for (pattern, typ) in &mut lambda.parameters {
+                    self.rename_pattern(pattern);
+                    self.rename_type(typ);
+                }
+                self.rename_type(&mut lambda.return_type);
+            }
+            ExpressionKind::TypePath(type_path) => {
+                self.rename_type(&mut type_path.typ);
+                if let Some(turbofish) = &mut type_path.turbofish {
+                    self.rename_generic_type_args(turbofish);
+                }
+            }
+            ExpressionKind::AsTraitPath(as_trait_path) => self.rename_as_trait_path(as_trait_path),
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        parser::{parse_program, ItemKind},
+        ParsedModule,
+    };
+
+    use super::rename_struct;
+
+    fn parse(src: &str) -> ParsedModule {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        module
+    }
+
+    #[test]
+    fn renames_struct_and_its_references() {
+        let mut module = parse(
+            "
+            struct Point { x: Field, y: Field }
+
+            impl Point {
+                fn origin() -> Point { Point { x: 0, y: 0 } }
+            }
+
+            fn norm(p: Point, ps: [Point; 2]) -> Field {
+                let Point { x, y } = p;
+                let q: Point = Point::origin();
+                let Point = x * x + y * y;
+                Point + q.x + ps[0].y
+            }
+            ",
+        );
+        rename_struct(&mut module, "Point", "Vec2");
+
+        let expected = parse(
+            "
+            struct Vec2 { x: Field, y: Field }
+
+            impl Vec2 {
+                fn origin() -> Vec2 { Vec2 { x: 0, y: 0 } }
+            }
+
+            fn norm(p: Vec2, ps: [Vec2; 2]) -> Field {
+                let Vec2 { x, y } = p;
+                let q: Vec2 = Vec2::origin();
+                let Point = x * x + y * y;
+                Point + q.x + ps[0].y
+            }
+            ",
+        );
+        assert_eq!(module.to_string(), expected.to_string());
+
+        let ItemKind::Struct(noir_struct) = &module.items[0].kind else {
+            panic!("Expected a struct");
+        };
+        assert_eq!(noir_struct.name.span(), Default::default());
+    }
+
+    #[test]
+    fn leaves_external_structs_of_the_same_name() {
+        let src = "
+            use dep::geometry::Point;
+
+            fn norm(p: Point, q: other::Point) -> Field { Point::norm(p) + q.x }
+            ";
+        let mut module = parse(src);
+        rename_struct(&mut module, "Point", "Vec2");
+        assert_eq!(module.to_string(), parse(src).to_string());
+
+        let mut module = parse(
+            "
+            use dep::geometry::Point as Point3;
+            use inner::{Point, helper};
+
+            mod inner {
+                struct Point { x: Field }
+                fn helper(p: self::Point) {}
+            }
+
+            fn norm(p: Point, q: inner::Point, r: Point3, s: other::Point) -> Field {
+                Point::norm(p) + inner::Point::norm(q) + other::Point::norm(s)
+            }
+            ",
+        );
+        rename_struct(&mut module, "Point", "Vec2");
+
+        let expected = parse(
+            "
+            use dep::geometry::Point as Point3;
+            use inner::{Vec2, helper};
+
+            mod inner {
+                struct Vec2 { x: Field }
+                fn helper(p: self::Vec2) {}
+            }
+
+            fn norm(p: Vec2, q: inner::Vec2, r: Point3, s: other::Point) -> Field {
+                Vec2::norm(p) + inner::Vec2::norm(q) + other::Point::norm(s)
+            }
+            ",
+        );
+        assert_eq!(module.to_string(), expected.to_string());
+    }
+}
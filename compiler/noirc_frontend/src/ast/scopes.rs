@@ -0,0 +1,369 @@
+use crate::{
+    ast::{
+        ArrayLiteral, BlockExpression, Expression, ExpressionKind, ForBounds, ForRange, Ident,
+        LValue, LetStatement, Literal, NoirFunction, Pattern, Statement, StatementKind,
+        TraitImplItemKind, TraitItem, UnresolvedGeneric, UnresolvedGenerics, UnresolvedType,
+        UnresolvedTypeData,
+    },
+    parser::{Item, ItemKind},
+    ParsedModule,
+};
+
+/// An in-place rewrite of the variables of a [ParsedModule] which is aware of the scopes the
+/// variables are declared in, driven by [walk_module].
+///
+/// Local variables are declared by function and lambda parameters, `let` patterns and `for`
+/// loops. A new scope is entered for each function, lambda, block and `for` loop body. Every
+/// method has an empty default implementation.
+pub(crate) trait ScopedRewriter {
+    /// Called before walking the parameters of each function, method or default trait method,
+    /// along with the names of the generics in scope in it: its own and, in an impl or trait,
+    /// `Self` and the generics of the impl or trait.
+    fn enter_function(&mut self, _is_unconstrained: bool, _generics: &[String]) {}
+
+    fn enter_scope(&mut self) {}
+
+    fn exit_scope(&mut self) {}
+
+    /// Called on each block, including function bodies, once it has been walked and its scope
+    /// has been exited.
+    fn exit_block(&mut self, _: &mut BlockExpression) {}
+
+    /// Called on each identifier bound by a pattern, parameter or `for` loop, along with its type
+    /// if it's written in the source. For a `let` this is called once its initializer has been
+    /// walked, so the binding isn't in scope there.
+    fn visit_binding(&mut self, _: &mut Ident, _: Option<&UnresolvedType>) {}
+
+    /// Called on each [ExpressionKind::Variable] expression.
+    fn visit_variable(&mut self, _: &mut Expression) {}
+
+    /// Called on the variable being assigned to, as in `x = 1`, `x.field = 1` or `x[0] = 1`.
+    fn visit_assigned_variable(&mut self, _: &mut Ident) {}
+
+    /// Called before entering the scope of a lambda's parameters.
+    fn enter_lambda(&mut self) {}
+
+    /// Called on each [ExpressionKind::Lambda] expression once its scope has been exited.
+    fn exit_lambda(&mut self, _: &mut Expression) {}
+
+    /// Called on each `let` statement, including globals, once its initializer and pattern have
+    /// been walked.
+    fn exit_let(&mut self, _: &mut LetStatement) {}
+
+    /// Called on each expression once it has been walked, after any more specific method.
+    fn exit_expression(&mut self, _: &mut Expression) {}
+
+    /// Called on each statement once it has been walked.
+    fn exit_statement(&mut self, _: &mut Statement) {}
+}
+
+/// Walks the function bodies, global initializers and trait and impl constants of `module`,
+/// including those in inline submodules, calling `rewriter` in source order.
+///
+/// Quoted code, variables interpolated in format strings and attribute arguments aren't walked.
+pub(crate) fn walk_module(module: &mut ParsedModule, rewriter: &mut impl ScopedRewriter) {
+    Walker { rewriter, outer_generics: Vec::new() }.walk_items(&mut module.items);
+}
+
+/// Walks the parameters and body of `function` like [walk_module] does.
+pub(crate) fn walk_function(function: &mut NoirFunction, rewriter: &mut impl ScopedRewriter) {
+    Walker { rewriter, outer_generics: Vec::new() }.walk_function(function);
+}
+
+/// Walks `expression` like [walk_module] walks function bodies. Variables declared outside of
+/// `expression` aren't in scope.
+pub(crate) fn walk_expression(expression: &mut Expression, rewriter: &mut impl ScopedRewriter) {
+    Walker { rewriter, outer_generics: Vec::new() }.walk_expression(expression);
+}
+
+struct Walker<'a, R> {
+    rewriter: &'a mut R,
+    /// The names of the generics of the impl or trait being walked, including `Self`.
+    outer_generics: Vec<String>,
+}
+
+fn generic_names(generics: &UnresolvedGenerics) -> impl Iterator<Item = String> + '_ {
+    generics.iter().filter_map(|generic| match generic {
+        UnresolvedGeneric::Variable(ident) | UnresolvedGeneric::Numeric { ident, .. } => {
+            Some(ident.0.contents.clone())
+        }
+        UnresolvedGeneric::Resolved(..) => None,
+    })
+}
+
+impl<R: ScopedRewriter> Walker<'_, R> {
+    fn walk_items(&mut self, items: &mut [Item]) {
+        for item in items {
+            match &mut item.kind {
+                ItemKind::Function(function) => self.walk_function(function),
+                ItemKind::Impl(type_impl) => {
+                    self.enter_impl_or_trait(&type_impl.generics);
+                    for (method, _) in &mut type_impl.methods {
+                        self.walk_function(&mut method.item);
+                    }
+                    self.outer_generics.clear();
+                }
+                ItemKind::TraitImpl(trait_impl) => {
+                    self.enter_impl_or_trait(&trait_impl.impl_generics);
+                    for item in &mut trait_impl.items {
+                        match &mut item.item.kind {
+                            TraitImplItemKind::Function(function) => self.walk_function(function),
+                            TraitImplItemKind::Constant(_, _, expression) => {
+                                self.walk_expression(expression);
+                            }
+                            TraitImplItemKind::Type { .. } => (),
+                        }
+                    }
+                    self.outer_generics.clear();
+                }
+                ItemKind::Trait(noir_trait) => {
+                    self.enter_impl_or_trait(&noir_trait.generics);
+                    for item in &mut noir_trait.items {
+                        match &mut item.item {
+                            TraitItem::Function {
+                                is_unconstrained,
+                                generics,
+                                parameters,
+                                body: Some(body),
+                                ..
+                            } => {
+                                let generics = self.generics_in_scope(generics);
+                                self.rewriter.enter_function(*is_unconstrained, &generics);
+                                self.rewriter.enter_scope();
+                                for (name, typ) in parameters {
+                                    self.rewriter.visit_binding(name, Some(&*typ));
+                                }
+                                self.walk_block(body);
+                                self.rewriter.exit_scope();
+                            }
+                            TraitItem::Constant { default_value: Some(expression), .. } => {
+                                self.walk_expression(expression);
+                            }
+                            TraitItem::Function { .. }
+                            | TraitItem::Constant { .. }
+                            | TraitItem::Type { .. } => (),
+                        }
+                    }
+                    self.outer_generics.clear();
+                }
+                ItemKind::Global(let_statement, _) => {
+                    self.walk_expression(&mut let_statement.expression);
+                    self.rewriter.exit_let(let_statement);
+                }
+                ItemKind::Submodules(submodule) => self.walk_items(&mut submodule.contents.items),
+                ItemKind::Import(..)
+                | ItemKind::Struct(_)
+                | ItemKind::TypeAlias(_)
+                | ItemKind::ModuleDecl(_)
+                | ItemKind::InnerAttribute(_) => (),
+            }
+        }
+    }
+
+    fn enter_impl_or_trait(&mut self, generics: &UnresolvedGenerics) {
+        self.outer_generics =
+            std::iter::once("Self".to_string()).chain(generic_names(generics)).collect();
+    }
+
+    /// Returns the names of the generics in scope in a function declaring `generics`.
+    fn generics_in_scope(&self, generics: &UnresolvedGenerics) -> Vec<String> {
+        self.outer_generics.iter().cloned().chain(generic_names(generics)).collect()
+    }
+
+    fn walk_function(&mut self, function: &mut NoirFunction) {
+        let generics = self.generics_in_scope(&function.def.generics);
+        self.rewriter.enter_function(function.def.is_unconstrained, &generics);
+        self.rewriter.enter_scope();
+        for parameter in &mut function.def.parameters {
+            self.walk_pattern(&mut parameter.pattern, Some(&parameter.typ));
+        }
+        self.walk_block(&mut function.def.body);
+        self.rewriter.exit_scope();
+    }
+
+    fn walk_block(&mut self, block: &mut BlockExpression) {
+        self.rewriter.enter_scope();
+        for statement in &mut block.statements {
+            self.walk_statement(statement);
+        }
+        self.rewriter.exit_scope();
+        self.rewriter.exit_block(block);
+    }
+
+    fn walk_statement(&mut self, statement: &mut Statement) {
+        match &mut statement.kind {
+            StatementKind::Let(let_statement) => {
+                self.walk_expression(&mut let_statement.expression);
+                self.walk_pattern(&mut let_statement.pattern, Some(&let_statement.r#type));
+                self.rewriter.exit_let(let_statement);
+            }
+            StatementKind::Constrain(constrain) => self.walk_expressions(&mut constrain.arguments),
+            StatementKind::Expression(expression) | StatementKind::Semi(expression) => {
+                self.walk_expression(expression);
+            }
+            StatementKind::Assign(assign) => {
+                self.walk_expression(&mut assign.expression);
+                self.walk_lvalue(&mut assign.lvalue);
+            }
+            StatementKind::For(for_loop) => {
+                match &mut for_loop.range {
+                    ForRange::Range(ForBounds { start, end, .. }) => {
+                        self.walk_expression(start);
+                        self.walk_expression(end);
+                    }
+                    ForRange::Array(expression) => self.walk_expression(expression),
+                }
+                self.rewriter.enter_scope();
+                self.rewriter.visit_binding(&mut for_loop.identifier, None);
+                self.walk_expression(&mut for_loop.block);
+                self.rewriter.exit_scope();
+            }
+            StatementKind::Comptime(statement) => self.walk_statement(statement),
+            StatementKind::Break
+            | StatementKind::Continue
+            | StatementKind::Interned(_)
+            | StatementKind::Error => (),
+        }
+        self.rewriter.exit_statement(statement);
+    }
+
+    /// Walks `pattern`, matching it against `typ` where possible to find the type of each binding.
+    fn walk_pattern(&mut self, pattern: &mut Pattern, typ: Option<&UnresolvedType>) {
+        let typ = typ.filter(|typ| !matches!(typ.typ, UnresolvedTypeData::Unspecified));
+        match pattern {
+            Pattern::Identifier(ident) => self.rewriter.visit_binding(ident, typ),
+            Pattern::Mutable(pattern, ..) => self.walk_pattern(pattern, typ),
+            Pattern::Tuple(patterns, _) => {
+                let types: Vec<_> = match typ.map(|typ| &typ.typ) {
+                    Some(UnresolvedTypeData::Tuple(types)) if types.len() == patterns.len() => {
+                        types.iter().map(Some).collect()
+                    }
+                    _ => vec![None; patterns.len()],
+                };
+                for (pattern, typ) in patterns.iter_mut().zip(types) {
+                    self.walk_pattern(pattern, typ);
+                }
+            }
+            Pattern::Struct(_, fields, _) => {
+                for (_, pattern) in fields {
+                    self.walk_pattern(pattern, None);
+                }
+            }
+            Pattern::Interned(..) => (),
+        }
+    }
+
+    fn walk_lvalue(&mut self, lvalue: &mut LValue) {
+        match lvalue {
+            LValue::Ident(ident) => self.rewriter.visit_assigned_variable(ident),
+            LValue::MemberAccess { object, .. } => self.walk_lvalue(object),
+            LValue::Index { array, index, .. } => {
+                self.walk_expression(index);
+                self.walk_lvalue(array);
+            }
+            LValue::Dereference(lvalue, _) => self.walk_lvalue(lvalue),
+            LValue::Interned(..) => (),
+        }
+    }
+
+    fn walk_expressions(&mut self, expressions: &mut [Expression]) {
+        for expression in expressions {
+            self.walk_expression(expression);
+        }
+    }
+
+    fn walk_expression(&mut self, expression: &mut Expression) {
+        self.walk_expression_kind(expression);
+        self.rewriter.exit_expression(expression);
+    }
+
+    fn walk_expression_kind(&mut self, expression: &mut Expression) {
+        if matches!(expression.kind, ExpressionKind::Variable(_)) {
+            self.rewriter.visit_variable(expression);
+            return;
+        }
+
+        if let ExpressionKind::Lambda(lambda) = &mut expression.kind {
+            self.rewriter.enter_lambda();
+            self.rewriter.enter_scope();
+            for (pattern, typ) in &mut lambda.parameters {
+                self.walk_pattern(pattern, Some(&*typ));
+            }
+            self.walk_expression(&mut lambda.body);
+            self.rewriter.exit_scope();
+            self.rewriter.exit_lambda(expression);
+            return;
+        }
+
+        match &mut expression.kind {
+            ExpressionKind::Literal(literal) => self.walk_literal(literal),
+            ExpressionKind::Block(block)
+            | ExpressionKind::Comptime(block, _)
+            | ExpressionKind::Unsafe(block, _) => self.walk_block(block),
+            ExpressionKind::Prefix(prefix) => self.walk_expression(&mut prefix.rhs),
+            ExpressionKind::Index(index) => {
+                self.walk_expression(&mut index.collection);
+                self.walk_expression(&mut index.index);
+            }
+            ExpressionKind::Call(call) => {
+                self.walk_expression(&mut call.func);
+                self.walk_expressions(&mut call.arguments);
+            }
+            ExpressionKind::MethodCall(method_call) => {
+                self.walk_expression(&mut method_call.object);
+                self.walk_expressions(&mut method_call.arguments);
+            }
+            ExpressionKind::Constructor(constructor) => {
+                for (_, expression) in &mut constructor.fields {
+                    self.walk_expression(expression);
+                }
+            }
+            ExpressionKind::MemberAccess(member_access) => {
+                self.walk_expression(&mut member_access.lhs);
+            }
+            ExpressionKind::Cast(cast) => self.walk_expression(&mut cast.lhs),
+            ExpressionKind::Infix(infix) => {
+                self.walk_expression(&mut infix.lhs);
+                self.walk_expression(&mut infix.rhs);
+            }
+            ExpressionKind::If(if_expression) => {
+                self.walk_expression(&mut if_expression.condition);
+                self.walk_expression(&mut if_expression.consequence);
+                if let Some(alternative) = &mut if_expression.alternative {
+                    self.walk_expression(alternative);
+                }
+            }
+            ExpressionKind::Tuple(expressions) => self.walk_expressions(expressions),
+            ExpressionKind::Parenthesized(expression) | ExpressionKind::Unquote(expression) => {
+                self.walk_expression(expression);
+            }
+            ExpressionKind::Variable(_)
+            | ExpressionKind::Lambda(_)
+            | ExpressionKind::Quote(_)
+            | ExpressionKind::AsTraitPath(_)
+            | ExpressionKind::TypePath(_)
+            | ExpressionKind::Resolved(_)
+            | ExpressionKind::Interned(_)
+            | ExpressionKind::InternedStatement(_)
+            | ExpressionKind::Error => (),
+        }
+    }
+
+    fn walk_literal(&mut self, literal: &mut Literal) {
+        match literal {
+            Literal::Array(ArrayLiteral::Standard(elements))
+            | Literal::Slice(ArrayLiteral::Standard(elements)) => self.walk_expressions(elements),
+            Literal::Array(ArrayLiteral::Repeated { repeated_element, length })
+            | Literal::Slice(ArrayLiteral::Repeated { repeated_element, length }) => {
+                self.walk_expression(repeated_element);
+                self.walk_expression(length);
+            }
+            Literal::Bool(_)
+            | Literal::Integer(..)
+            | Literal::Str(_)
+            | Literal::RawStr(..)
+            | Literal::FmtStr(_)
+            | Literal::Unit => (),
+        }
+    }
+}
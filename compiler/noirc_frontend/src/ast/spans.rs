@@ -0,0 +1,292 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        AsTraitPath, BlockExpression, Expression, ExpressionKind, FunctionReturnType,
+        GenericTypeArgs, Ident, LValue, NoirFunction, Path, Pattern, Statement, StatementKind,
+        UnresolvedGeneric, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
+    },
+    token::{SecondaryAttribute, SpannedToken},
+};
+
+use super::fold::map_expression;
+
+/// Returns `expression` with the default span on every expression, statement, identifier, path,
+/// pattern and type in it, so that nothing in it points into the source it was taken from.
+pub(super) fn clear_spans(expression: Expression) -> Expression {
+    map_expression(expression, clear_expression_spans)
+}
+
+/// Gives the default span to every part of `function`, including its name, attributes, generics,
+/// parameters, return type, where clause and body, like [clear_spans] does for an expression.
+pub(super) fn clear_function_spans(function: &mut NoirFunction) {
+    let def = &mut function.def;
+    clear_ident_span(&mut def.name);
+    for attribute in &mut def.attributes.secondary {
+        clear_attribute_spans(attribute);
+    }
+    for generic in &mut def.generics {
+        match generic {
+            UnresolvedGeneric::Variable(ident) => clear_ident_span(ident),
+            UnresolvedGeneric::Numeric { ident, typ } => {
+                clear_ident_span(ident);
+                clear_type_spans(typ);
+            }
+            UnresolvedGeneric::Resolved(_, span) => *span = Span::default(),
+        }
+    }
+    for parameter in &mut def.parameters {
+        clear_pattern_spans(&mut parameter.pattern);
+        clear_type_spans(&mut parameter.typ);
+        parameter.span = Span::default();
+    }
+    match &mut def.return_type {
+        FunctionReturnType::Default(span) => *span = Span::default(),
+        FunctionReturnType::Ty(typ) => clear_type_spans(typ),
+    }
+    for constraint in &mut def.where_clause {
+        clear_type_spans(&mut constraint.typ);
+        clear_path_spans(&mut constraint.trait_bound.trait_path);
+        clear_generic_type_args_spans(&mut constraint.trait_bound.trait_generics);
+    }
+    def.span = Span::default();
+
+    let body = std::mem::replace(&mut def.body, BlockExpression { statements: Vec::new() });
+    let body = clear_spans(Expression::new(ExpressionKind::Block(body), Span::default()));
+    let ExpressionKind::Block(body) = body.kind else {
+        unreachable!("Clearing spans keeps the kind of an expression");
+    };
+    def.body = body;
+}
+
+fn clear_attribute_spans(attribute: &mut SecondaryAttribute) {
+    match attribute {
+        SecondaryAttribute::Tag(attribute) => {
+            attribute.span = Span::default();
+            attribute.contents_span = Span::default();
+        }
+        SecondaryAttribute::Meta(attribute) => {
+            clear_path_spans(&mut attribute.name);
+            let arguments = std::mem::take(&mut attribute.arguments);
+            attribute.arguments = arguments.into_iter().map(clear_spans).collect();
+            attribute.span = Span::default();
+        }
+        SecondaryAttribute::Deprecated(_)
+        | SecondaryAttribute::ContractLibraryMethod
+        | SecondaryAttribute::Export
+        | SecondaryAttribute::Field(_)
+        | SecondaryAttribute::Abi(_)
+        | SecondaryAttribute::Varargs
+        | SecondaryAttribute::UseCallersScope
+        | SecondaryAttribute::Allow(_) => (),
+    }
+}
+
+/// Clears the spans of the parts of `expression` which aren't expressions themselves, as those
+/// have already been cleared by [map_expression].
+fn clear_expression_spans(mut expression: Expression) -> Expression {
+    match &mut expression.kind {
+        ExpressionKind::Block(block) => {
+            block.statements.iter_mut().for_each(clear_statement_spans);
+        }
+        ExpressionKind::Comptime(block, span) | ExpressionKind::Unsafe(block, span) => {
+            block.statements.iter_mut().for_each(clear_statement_spans);
+            *span = Span::default();
+        }
+        ExpressionKind::Infix(infix) => infix.operator.set_span(Span::default()),
+        ExpressionKind::MethodCall(method_call) => {
+            clear_ident_span(&mut method_call.method_name);
+            method_call.generics.iter_mut().flatten().for_each(clear_type_spans);
+        }
+        ExpressionKind::Constructor(constructor) => {
+            clear_type_spans(&mut constructor.typ);
+            for (name, _) in &mut constructor.fields {
+                clear_ident_span(name);
+            }
+        }
+        ExpressionKind::MemberAccess(member_access) => clear_ident_span(&mut member_access.rhs),
+        ExpressionKind::Cast(cast) => clear_type_spans(&mut cast.r#type),
+        ExpressionKind::Variable(path) => clear_path_spans(path),
+        ExpressionKind::Lambda(lambda) => {
+            for (pattern, typ) in &mut lambda.parameters {
+                clear_pattern_spans(pattern);
+                clear_type_spans(typ);
+            }
+            clear_type_spans(&mut lambda.return_type);
+        }
+        ExpressionKind::Quote(tokens) => {
+            for token in &mut tokens.0 {
+                *token = SpannedToken::new(token.token().clone(), Span::default());
+            }
+        }
+        ExpressionKind::AsTraitPath(as_trait_path) => clear_as_trait_path_spans(as_trait_path),
+        ExpressionKind::TypePath(type_path) => {
+            clear_type_spans(&mut type_path.typ);
+            clear_ident_span(&mut type_path.item);
+            if let Some(turbofish) = &mut type_path.turbofish {
+                clear_generic_type_args_spans(turbofish);
+            }
+        }
+        ExpressionKind::Literal(_)
+        | ExpressionKind::Prefix(_)
+        | ExpressionKind::Index(_)
+        | ExpressionKind::Call(_)
+        | ExpressionKind::If(_)
+        | ExpressionKind::Tuple(_)
+        | ExpressionKind::Parenthesized(_)
+        | ExpressionKind::Unquote(_)
+        | ExpressionKind::Resolved(_)
+        | ExpressionKind::Interned(_)
+        | ExpressionKind::InternedStatement(_)
+        | ExpressionKind::Error => (),
+    }
+    expression.span = Span::default();
+    expression
+}
+
+fn clear_statement_spans(statement: &mut Statement) {
+    match &mut statement.kind {
+        StatementKind::Let(let_statement) => {
+            clear_pattern_spans(&mut let_statement.pattern);
+            clear_type_spans(&mut let_statement.r#type);
+        }
+        StatementKind::Assign(assign) => clear_lvalue_spans(&mut assign.lvalue),
+        StatementKind::For(for_loop) => {
+            clear_ident_span(&mut for_loop.identifier);
+            for_loop.span = Span::default();
+        }
+        StatementKind::Constrain(constrain) => constrain.span = Span::default(),
+        StatementKind::Comptime(statement) => clear_statement_spans(statement),
+        StatementKind::Expression(_)
+        | StatementKind::Semi(_)
+        | StatementKind::Break
+        | StatementKind::Continue
+        | StatementKind::Interned(_)
+        | StatementKind::Error => (),
+    }
+    statement.span = Span::default();
+}
+
+fn clear_ident_span(ident: &mut Ident) {
+    ident.0.set_span(Span::default());
+}
+
+fn clear_path_spans(path: &mut Path) {
+    path.span = Span::default();
+    for segment in &mut path.segments {
+        clear_ident_span(&mut segment.ident);
+        segment.generics.iter_mut().flatten().for_each(clear_type_spans);
+        segment.span = Span::default();
+    }
+}
+
+fn clear_pattern_spans(pattern: &mut Pattern) {
+    match pattern {
+        Pattern::Identifier(ident) => clear_ident_span(ident),
+        Pattern::Mutable(pattern, span, _) => {
+            clear_pattern_spans(pattern);
+            *span = Span::default();
+        }
+        Pattern::Tuple(patterns, span) => {
+            patterns.iter_mut().for_each(clear_pattern_spans);
+            *span = Span::default();
+        }
+        Pattern::Struct(path, fields, span) => {
+            clear_path_spans(path);
+            for (name, pattern) in fields {
+                clear_ident_span(name);
+                clear_pattern_spans(pattern);
+            }
+            *span = Span::default();
+        }
+        Pattern::Interned(_, span) => *span = Span::default(),
+    }
+}
+
+fn clear_lvalue_spans(lvalue: &mut LValue) {
+    match lvalue {
+        LValue::Ident(ident) => clear_ident_span(ident),
+        LValue::MemberAccess { object, field_name, span } => {
+            clear_lvalue_spans(object);
+            clear_ident_span(field_name);
+            *span = Span::default();
+        }
+        LValue::Index { array, span, .. } => {
+            clear_lvalue_spans(array);
+            *span = Span::default();
+        }
+        LValue::Dereference(lvalue, span) => {
+            clear_lvalue_spans(lvalue);
+            *span = Span::default();
+        }
+        LValue::Interned(_, span) => *span = Span::default(),
+    }
+}
+
+fn clear_type_spans(typ: &mut UnresolvedType) {
+    match &mut typ.typ {
+        UnresolvedTypeData::Expression(expression) | UnresolvedTypeData::String(expression) => {
+            clear_type_expression_spans(expression);
+        }
+        UnresolvedTypeData::Array(length, element)
+        | UnresolvedTypeData::FormatString(length, element) => {
+            clear_type_expression_spans(length);
+            clear_type_spans(element);
+        }
+        UnresolvedTypeData::Slice(element)
+        | UnresolvedTypeData::Parenthesized(element)
+        | UnresolvedTypeData::MutableReference(element) => clear_type_spans(element),
+        UnresolvedTypeData::Named(path, generics, _)
+        | UnresolvedTypeData::TraitAsType(path, generics) => {
+            clear_path_spans(path);
+            clear_generic_type_args_spans(generics);
+        }
+        UnresolvedTypeData::Tuple(elements) => elements.iter_mut().for_each(clear_type_spans),
+        UnresolvedTypeData::Function(arguments, return_type, environment, _) => {
+            arguments.iter_mut().for_each(clear_type_spans);
+            clear_type_spans(return_type);
+            clear_type_spans(environment);
+        }
+        UnresolvedTypeData::AsTraitPath(as_trait_path) => clear_as_trait_path_spans(as_trait_path),
+        UnresolvedTypeData::FieldElement
+        | UnresolvedTypeData::Integer(..)
+        | UnresolvedTypeData::Bool
+        | UnresolvedTypeData::Unit
+        | UnresolvedTypeData::Quoted(_)
+        | UnresolvedTypeData::Resolved(_)
+        | UnresolvedTypeData::Interned(_)
+        | UnresolvedTypeData::Unspecified
+        | UnresolvedTypeData::Error => (),
+    }
+    typ.span = Span::default();
+}
+
+fn clear_type_expression_spans(expression: &mut UnresolvedTypeExpression) {
+    match expression {
+        UnresolvedTypeExpression::Variable(path) => clear_path_spans(path),
+        UnresolvedTypeExpression::Constant(_, span) => *span = Span::default(),
+        UnresolvedTypeExpression::BinaryOperation(lhs, _, rhs, span) => {
+            clear_type_expression_spans(lhs);
+            clear_type_expression_spans(rhs);
+            *span = Span::default();
+        }
+        UnresolvedTypeExpression::AsTraitPath(as_trait_path) => {
+            clear_as_trait_path_spans(as_trait_path);
+        }
+    }
+}
+
+fn clear_generic_type_args_spans(generics: &mut GenericTypeArgs) {
+    generics.ordered_args.iter_mut().for_each(clear_type_spans);
+    for (name, typ) in &mut generics.named_args {
+        clear_ident_span(name);
+        clear_type_spans(typ);
+    }
+}
+
+fn clear_as_trait_path_spans(as_trait_path: &mut AsTraitPath) {
+    clear_type_spans(&mut as_trait_path.typ);
+    clear_path_spans(&mut as_trait_path.trait_path);
+    clear_generic_type_args_spans(&mut as_trait_path.trait_generics);
+    clear_ident_span(&mut as_trait_path.impl_item);
+}
@@ -0,0 +1,399 @@
+use noirc_errors::Span;
+
+use crate::ast::{
+    inline_global::VariableSubstituter,
+    scopes::{walk_function, ScopedRewriter},
+    spans::clear_function_spans,
+    AsTraitPath, Expression, ExpressionKind, FunctionReturnType, GenericTypeArgKind,
+    GenericTypeArgs, Ident, LetStatement, NoirFunction, Path, PathKind, PathSegment, TypePath,
+    UnresolvedGeneric, UnresolvedType, UnresolvedTypeData, UnresolvedTypeExpression,
+};
+
+/// Returns a copy of `function` where the generic named `generic` has been replaced by the type
+/// `concrete`, and removed from the function's generics.
+///
+/// The generic is replaced in the parameter types, return type and where clause, and in every
+/// type written in the body: `let` annotations, casts, constructors, lambda signatures, turbofish
+/// generics and array or string lengths. A path such as `T::default()` becomes a call on the
+/// concrete type. When `generic` is numeric, as in `let N: u32`, and `concrete` is a constant or
+/// names another generic, uses of `N` as a value are replaced too.
+///
+/// The copy is named after `function` and `concrete`, e.g. `foo_Field` or `foo_u8_3` for
+/// `[u8; 3]`, so that it can sit next to the original. Every span of the copy is reset to the
+/// default span, so that nothing in it points into `function`.
+pub fn specialize_function(
+    function: &NoirFunction,
+    generic: &str,
+    concrete: UnresolvedType,
+) -> NoirFunction {
+    let mut specialized = function.clone();
+
+    let mut is_numeric = false;
+    specialized.def.generics.retain(|unresolved_generic| match unresolved_generic {
+        UnresolvedGeneric::Variable(ident) => ident.0.contents != generic,
+        UnresolvedGeneric::Numeric { ident, .. } => {
+            is_numeric |= ident.0.contents == generic;
+            ident.0.contents != generic
+        }
+        UnresolvedGeneric::Resolved(..) => true,
+    });
+
+    let mut substituter = TypeSubstituter { generic, concrete: &concrete };
+    let def = &mut specialized.def;
+    for parameter in &mut def.parameters {
+        substituter.substitute_type(&mut parameter.typ);
+    }
+    if let FunctionReturnType::Ty(return_type) = &mut def.return_type {
+        substituter.substitute_type(return_type);
+    }
+    for constraint in &mut def.where_clause {
+        substituter.substitute_type(&mut constraint.typ);
+        substituter.substitute_path(&mut constraint.trait_bound.trait_path);
+        substituter.substitute_generic_type_args(&mut constraint.trait_bound.trait_generics);
+    }
+    def.name = Ident::from(format!("{}_{}", def.name, mangle(&concrete)));
+
+    walk_function(&mut specialized, &mut substituter);
+
+    if is_numeric {
+        if let Some(value) = numeric_value(&concrete) {
+            walk_function(&mut specialized, &mut VariableSubstituter::new(generic, value, false));
+        }
+    }
+
+    clear_function_spans(&mut specialized);
+
+    specialized
+}
+
+/// Returns the words of `typ`'s source form joined by underscores, e.g. `u8_3` for `[u8; 3]`.
+fn mangle(typ: &UnresolvedType) -> String {
+    let typ = typ.to_string();
+    let words = typ.split(|char: char| !char.is_ascii_alphanumeric() && char != '_');
+    words.filter(|word| !word.is_empty()).collect::<Vec<_>>().join("_")
+}
+
+/// Returns the expression a numeric generic specialized to `concrete` evaluates to, if any.
+fn numeric_value(concrete: &UnresolvedType) -> Option<Expression> {
+    let kind = match &concrete.typ {
+        UnresolvedTypeData::Expression(UnresolvedTypeExpression::Constant(value, _)) => {
+            ExpressionKind::integer(*value)
+        }
+        UnresolvedTypeData::Expression(UnresolvedTypeExpression::Variable(path))
+            if path.is_ident() =>
+        {
+            ExpressionKind::Variable(path.clone())
+        }
+        UnresolvedTypeData::Named(path, generics, _) if generics.is_empty() && path.is_ident() => {
+            ExpressionKind::Variable(path.clone())
+        }
+        _ => return None,
+    };
+    Some(Expression::new(kind, Span::default()))
+}
+
+struct TypeSubstituter<'a> {
+    generic: &'a str,
+    concrete: &'a UnresolvedType,
+}
+
+impl TypeSubstituter<'_> {
+    fn is_generic(&self, path: &Path) -> bool {
+        path.as_ident().map_or(false, |ident| ident.0.contents == self.generic)
+    }
+
+    fn substitute_type(&self, typ: &mut UnresolvedType) {
+        match &mut typ.typ {
+            UnresolvedTypeData::Named(path, generics, _) => {
+                if generics.is_empty() && self.is_generic(path) {
+                    *typ = self.concrete.clone();
+                    return;
+                }
+                self.substitute_path(path);
+                self.substitute_generic_type_args(generics);
+            }
+            UnresolvedTypeData::Array(length, element) => {
+                self.substitute_type_expression(length);
+                self.substitute_type(element);
+            }
+            UnresolvedTypeData::Slice(element)
+            | UnresolvedTypeData::Parenthesized(element)
+            | UnresolvedTypeData::MutableReference(element) => self.substitute_type(element),
+            UnresolvedTypeData::Expression(expression) | UnresolvedTypeData::String(expression) => {
+                self.substitute_type_expression(expression);
+            }
+            UnresolvedTypeData::FormatString(length, element) => {
+                self.substitute_type_expression(length);
+                self.substitute_type(element);
+            }
+            UnresolvedTypeData::TraitAsType(path, generics) => {
+                self.substitute_path(path);
+                self.substitute_generic_type_args(generics);
+            }
+            UnresolvedTypeData::Tuple(elements) => {
+                for element in elements {
+                    self.substitute_type(element);
+                }
+            }
+            UnresolvedTypeData::Function(arguments, return_type, environment, _) => {
+                for argument in arguments {
+                    self.substitute_type(argument);
+                }
+                self.substitute_type(return_type);
+                self.substitute_type(environment);
+            }
+            UnresolvedTypeData::AsTraitPath(as_trait_path) => {
+                self.substitute_as_trait_path(as_trait_path);
+            }
+            UnresolvedTypeData::FieldElement
+            | UnresolvedTypeData::Integer(..)
+            | UnresolvedTypeData::Bool
+            | UnresolvedTypeData::Unit
+            | UnresolvedTypeData::Quoted(_)
+            | UnresolvedTypeData::Resolved(_)
+            | UnresolvedTypeData::Interned(_)
+            | UnresolvedTypeData::Unspecified
+            | UnresolvedTypeData::Error => (),
+        }
+    }
+
+    fn substitute_type_expression(&self, expression: &mut UnresolvedTypeExpression) {
+        match expression {
+            UnresolvedTypeExpression::Variable(path) => {
+                if !self.is_generic(path) {
+                    return;
+                }
+                // Only a type which is itself a type expression can be used as one
+                match &self.concrete.typ {
+                    UnresolvedTypeData::Expression(concrete) => *expression = concrete.clone(),
+                    UnresolvedTypeData::Named(path, generics, _) if generics.is_empty() => {
+                        *expression = UnresolvedTypeExpression::Variable(path.clone());
+                    }
+                    _ => (),
+                }
+            }
+            UnresolvedTypeExpression::Constant(..) => (),
+            UnresolvedTypeExpression::BinaryOperation(lhs, _, rhs, _) => {
+                self.substitute_type_expression(lhs);
+                self.substitute_type_expression(rhs);
+            }
+            UnresolvedTypeExpression::AsTraitPath(as_trait_path) => {
+                self.substitute_as_trait_path(as_trait_path);
+            }
+        }
+    }
+
+    fn substitute_path(&self, path: &mut Path) {
+        for segment in &mut path.segments {
+            for typ in segment.generics.iter_mut().flatten() {
+                self.substitute_type(typ);
+            }
+        }
+    }
+
+    fn substitute_generic_type_args(&self, generics: &mut GenericTypeArgs) {
+        for typ in &mut generics.ordered_args {
+            self.substitute_type(typ);
+        }
+        for (_, typ) in &mut generics.named_args {
+            self.substitute_type(typ);
+        }
+    }
+
+    fn substitute_as_trait_path(&self, as_trait_path: &mut AsTraitPath) {
+        self.substitute_type(&mut as_trait_path.typ);
+        self.substitute_path(&mut as_trait_path.trait_path);
+        self.substitute_generic_type_args(&mut as_trait_path.trait_generics);
+    }
+
+    /// Returns whether `path` is `T::item`, where `T` is the generic being substituted.
+    fn is_generic_type_path(&self, path: &Path) -> bool {
+        path.kind == PathKind::Plain
+            && path.segments.len() == 2
+            && path.segments[0].generics.is_none()
+            && path.segments[0].ident.0.contents == self.generic
+    }
+}
+
+impl ScopedRewriter for TypeSubstituter<'_> {
+    fn exit_let(&mut self, let_statement: &mut LetStatement) {
+        self.substitute_type(&mut let_statement.r#type);
+    }
+
+    fn exit_expression(&mut self, expression: &mut Expression) {
+        match &mut expression.kind {
+            ExpressionKind::Variable(path) => {
+                self.substitute_path(path);
+                if self.is_generic_type_path(path) {
+                    let PathSegment { ident: item, generics, .. } = path.segments[1].clone();
+                    let turbofish = generics.map(|generics| {
+                        let kinds = vec![GenericTypeArgKind::Ordered; generics.len()];
+                        GenericTypeArgs { ordered_args: generics, named_args: Vec::new(), kinds }
+                    });
+                    let typ = self.concrete.clone();
+                    expression.kind = ExpressionKind::TypePath(TypePath { typ, item, turbofish });
+                }
+            }
+            ExpressionKind::Cast(cast) => self.substitute_type(&mut cast.r#type),
+            ExpressionKind::Constructor(constructor) => {
+                self.substitute_type(&mut constructor.typ);
+            }
+            ExpressionKind::MethodCall(method_call) => {
+                for typ in method_call.generics.iter_mut().flatten() {
+                    self.substitute_type(typ);
+                }
+            }
+            ExpressionKind::Lambda(lambda) => {
+                for (_, typ) in &mut lambda.parameters {
+                    self.substitute_type(typ);
+                }
+                self.substitute_type(&mut lambda.return_type);
+            }
+            ExpressionKind::TypePath(type_path) => {
+                self.substitute_type(&mut type_path.typ);
+                if let Some(turbofish) = &mut type_path.turbofish {
+                    self.substitute_generic_type_args(turbofish);
+                }
+            }
+            ExpressionKind::AsTraitPath(as_trait_path) => {
+                self.substitute_as_trait_path(as_trait_path);
+            }
+            _ => (),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+    use noirc_errors::Span;
+
+    use crate::{
+        ast::{ExpressionKind, NoirFunction, StatementKind, UnresolvedType, UnresolvedTypeData},
+        parser::{parse_program, ItemKind},
+    };
+
+    use super::specialize_function;
+
+    fn parse_function(src: &str) -> NoirFunction {
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        match module.items.remove(0).kind {
+            ItemKind::Function(function) => function,
+            _ => panic!("Expected a function"),
+        }
+    }
+
+    /// Parses `typ` as the type of a function parameter.
+    fn parse_type(typ: &str) -> UnresolvedType {
+        parse_function(&format!("fn f(x: {typ}) {{}}")).def.parameters.remove(0).typ
+    }
+
+    /// Returns the length of the array type `typ`, as a type.
+    fn array_length(typ: UnresolvedType) -> UnresolvedType {
+        let UnresolvedTypeData::Array(length, _) = typ.typ else {
+            panic!("Expected an array type");
+        };
+        UnresolvedType { typ: UnresolvedTypeData::Expression(length), span: typ.span }
+    }
+
+    fn signature_strings(function: &NoirFunction) -> Vec<String> {
+        let mut strings = vec![function.def.name.to_string()];
+        strings.extend(function.def.generics.iter().map(ToString::to_string));
+        strings.extend(
+            function.def.parameters.iter().map(|param| format!("{}: {}", param.pattern, param.typ)),
+        );
+        strings.push(function.def.return_type.to_string());
+        strings.extend(function.def.where_clause.iter().map(ToString::to_string));
+        strings
+    }
+
+    fn body_strings(function: &NoirFunction) -> Vec<String> {
+        vecmap(&function.def.body.statements, |statement| statement.kind.to_string())
+    }
+
+    #[test]
+    fn specializes_over_field() {
+        let function = parse_function(
+            "
+            fn sum<T, let N: u32>(xs: [T; N], init: T) -> T where T: Add {
+                let mut total: T = init;
+                let zero = T::default();
+                let n = N;
+                (total + zero) as T
+            }
+            ",
+        );
+
+        let specialized = specialize_function(&function, "T", parse_type("Field"));
+        assert_eq!(
+            signature_strings(&specialized),
+            vec!["sum_Field", "let N: u32", "xs: [Field; N]", "init: Field", "Field", "Field: Add"]
+        );
+        assert_eq!(
+            body_strings(&specialized),
+            vec![
+                "let mut total: Field = init",
+                "let zero = Field::default()",
+                "let n = N",
+                "((total + zero) as Field)",
+            ]
+        );
+
+        let specialized = specialize_function(&function, "N", array_length(parse_type("[u8; 4]")));
+        assert_eq!(specialized.def.name.to_string(), "sum_4");
+        assert_eq!(specialized.def.parameters[0].typ.to_string(), "[T; 4]");
+        assert_eq!(body_strings(&specialized)[2], "let n = 4");
+    }
+
+    #[test]
+    fn specializes_over_array_type() {
+        let function = parse_function("fn first<T>(xs: [T; 2]) -> T { let x: T = xs[0]; x }");
+
+        let specialized = specialize_function(&function, "T", parse_type("[u8; 3]"));
+        assert_eq!(
+            signature_strings(&specialized),
+            vec!["first_u8_3", "xs: [[u8; 3]; 2]", "[u8; 3]"]
+        );
+        assert_eq!(body_strings(&specialized), vec!["let x: [u8; 3] = xs[0]", "x"]);
+    }
+
+    #[test]
+    fn clears_every_span() {
+        let function = parse_function(
+            "fn first<T, U>(xs: [T; 2], y: U) -> T where U: Eq { let x: T = xs[0]; x.foo(y) }",
+        );
+        let specialized = specialize_function(&function, "T", parse_type("Field"));
+
+        let def = &specialized.def;
+        assert_eq!(def.span, Span::default());
+        assert_eq!(def.name.span(), Span::default());
+        assert_eq!(def.generics[0].span(), Span::default());
+        for parameter in &def.parameters {
+            assert_eq!(parameter.span, Span::default());
+            assert_eq!(parameter.pattern.span(), Span::default());
+            assert_eq!(parameter.typ.span, Span::default());
+        }
+        assert_eq!(def.return_type.get_type().span, Span::default());
+        assert_eq!(def.where_clause[0].typ.span, Span::default());
+        assert_eq!(def.where_clause[0].trait_bound.trait_path.span, Span::default());
+
+        let StatementKind::Let(let_statement) = &def.body.statements[0].kind else {
+            panic!("Expected a let statement");
+        };
+        assert_eq!(def.body.statements[0].span, Span::default());
+        assert_eq!(let_statement.pattern.span(), Span::default());
+        assert_eq!(let_statement.r#type.span, Span::default());
+        assert_eq!(let_statement.expression.span, Span::default());
+
+        let StatementKind::Expression(method_call) = &def.body.statements[1].kind else {
+            panic!("Expected an expression");
+        };
+        let ExpressionKind::MethodCall(method_call) = &method_call.kind else {
+            panic!("Expected a method call");
+        };
+        assert_eq!(method_call.method_name.span(), Span::default());
+        assert_eq!(method_call.arguments[0].span, Span::default());
+    }
+}
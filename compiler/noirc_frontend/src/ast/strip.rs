@@ -0,0 +1,102 @@
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        scopes::{walk_module, ScopedRewriter},
+        Expression, ExpressionKind, Literal, Statement, StatementKind,
+    },
+    ParsedModule,
+};
+
+/// Replaces every `assert`, `assert_eq` and `constrain` statement of `module`, including those
+/// nested in loops, `if` expressions and lambdas, with the no-op statement `();`. The
+/// replacements get the default span.
+///
+/// This is only meant for measuring how much of a program's cost comes from its assertions, by
+/// comparing it with the stripped version. The stripped program accepts inputs the original
+/// rejects, so it must never be used to generate or verify real proofs.
+pub fn strip_constraints(module: &mut ParsedModule) {
+    walk_module(module, &mut ConstraintStripper);
+}
+
+struct ConstraintStripper;
+
+impl ScopedRewriter for ConstraintStripper {
+    fn exit_statement(&mut self, statement: &mut Statement) {
+        if matches!(statement.kind, StatementKind::Constrain(_)) {
+            let unit = Expression::new(ExpressionKind::Literal(Literal::Unit), Span::default());
+            statement.kind = StatementKind::Semi(unit);
+            statement.span = Span::default();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ast::{Statement, StatementKind, Visitor},
+        parser::parse_program,
+        ParsedModule,
+    };
+
+    use super::strip_constraints;
+
+    fn parse(src: &str) -> ParsedModule {
+        let (module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        module
+    }
+
+    #[derive(Default)]
+    struct ConstrainCounter {
+        count: usize,
+    }
+
+    impl Visitor for ConstrainCounter {
+        fn visit_statement(&mut self, statement: &Statement) -> bool {
+            if matches!(statement.kind, StatementKind::Constrain(_)) {
+                self.count += 1;
+            }
+            true
+        }
+    }
+
+    fn count_constrains(module: &ParsedModule) -> usize {
+        let mut counter = ConstrainCounter::default();
+        module.accept(&mut counter);
+        counter.count
+    }
+
+    #[test]
+    fn strips_every_constraint() {
+        let src = "
+        fn main(x: Field, y: pub Field) -> pub Field {
+            assert(x != 0);
+            let mut total = x;
+            for i in 0..3 {
+                assert_eq(i, i, \"message\");
+                total += i;
+            }
+            if y == 1 {
+                assert(total != y);
+            } else {
+                total = total * 2;
+            }
+            let f = |z: Field| { assert(z != 0); z };
+            f(total)
+        }
+        ";
+        let mut module = parse(src);
+        assert_eq!(count_constrains(&module), 4);
+
+        strip_constraints(&mut module);
+        assert_eq!(count_constrains(&module), 0);
+
+        let expected = src
+            .replace("assert(x != 0);", "();")
+            .replace("assert_eq(i, i, \"message\");", "();")
+            .replace("assert(total != y);", "();")
+            .replace("assert(z != 0);", "();");
+        assert_eq!(module.to_string(), parse(&expected).to_string());
+    }
+}
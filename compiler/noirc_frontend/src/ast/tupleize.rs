@@ -0,0 +1,253 @@
+use noirc_errors::Span;
+
+use crate::ast::{
+    analysis::generics_used_in_types, BlockExpression, ConstructorExpression, Documented,
+    Expression, ExpressionKind, FunctionReturnType, GenericTypeArgKind, GenericTypeArgs, Ident,
+    LetStatement, NoirFunction, NoirStruct, Path, Pattern, Statement, StatementKind, StructField,
+    UnresolvedGeneric, UnresolvedType, UnresolvedTypeData,
+};
+
+/// Makes `function` return a struct named `struct_name` instead of a tuple, returning the struct
+/// so that it can be added next to the function. Returns `None` and leaves `function` untouched
+/// if its return type isn't a tuple.
+///
+/// The struct has one field per tuple element, named `_0`, `_1`, ..., and takes the generics of
+/// `function` its fields use. Only the outermost tuple is converted: a nested tuple becomes the
+/// type of a field. Every tail expression of the body (including the tails of `if` branches and
+/// nested blocks) is wrapped in a constructor of the struct. A tail which is a tuple literal has
+/// its elements moved into the constructor, while any other tail is first destructured into
+/// `$tuple_0`, `$tuple_1`, ... variables. Generated nodes get the default span.
+pub fn tupleize_to_struct(function: &mut NoirFunction, struct_name: &str) -> Option<NoirStruct> {
+    let FunctionReturnType::Ty(return_type) = &function.def.return_type else {
+        return None;
+    };
+    let UnresolvedTypeData::Tuple(element_types) = &return_type.typ else {
+        return None;
+    };
+    if element_types.is_empty() {
+        return None;
+    }
+
+    let name = Ident::from(struct_name);
+    let generics: Vec<UnresolvedGeneric> =
+        generics_used_in_types(&function.def.generics, element_types)
+            .into_iter()
+            .cloned()
+            .collect();
+    let fields = element_types
+        .iter()
+        .enumerate()
+        .map(|(index, typ)| {
+            Documented::not_documented(StructField {
+                visibility: function.def.visibility,
+                name: field_name(index),
+                typ: typ.clone(),
+            })
+        })
+        .collect();
+    let noir_struct = NoirStruct {
+        name: name.clone(),
+        attributes: Vec::new(),
+        visibility: function.def.visibility,
+        generics,
+        fields,
+        span: Span::default(),
+    };
+
+    let rewriter = TailRewriter { struct_name: name, arity: element_types.len() };
+    rewriter.rewrite_block(&mut function.def.body);
+    function.def.return_type = FunctionReturnType::Ty(struct_type(&noir_struct));
+
+    Some(noir_struct)
+}
+
+fn field_name(index: usize) -> Ident {
+    Ident::from(format!("_{index}"))
+}
+
+/// Returns the type `Name<A, B, ...>` of `noir_struct`, instantiated with its own generics.
+fn struct_type(noir_struct: &NoirStruct) -> UnresolvedType {
+    let ordered_args: Vec<_> = noir_struct
+        .generics
+        .iter()
+        .map(|generic| {
+            let path = Path::from_ident(generic.ident().clone());
+            let typ = UnresolvedTypeData::Named(path, GenericTypeArgs::default(), false);
+            UnresolvedType { typ, span: Span::default() }
+        })
+        .collect();
+    let kinds = vec![GenericTypeArgKind::Ordered; ordered_args.len()];
+    let generics = GenericTypeArgs { ordered_args, named_args: Vec::new(), kinds };
+
+    let path = Path::from_ident(noir_struct.name.clone());
+    UnresolvedType { typ: UnresolvedTypeData::Named(path, generics, false), span: Span::default() }
+}
+
+struct TailRewriter {
+    struct_name: Ident,
+    arity: usize,
+}
+
+impl TailRewriter {
+    fn rewrite_block(&self, block: &mut BlockExpression) {
+        // A block without a tail expression can't have evaluated to a tuple in the first place
+        if let Some(Statement { kind: StatementKind::Expression(tail), .. }) =
+            block.statements.last_mut()
+        {
+            self.rewrite_tail(tail);
+        }
+    }
+
+    fn rewrite_tail(&self, expression: &mut Expression) {
+        match &mut expression.kind {
+            ExpressionKind::Block(block)
+            | ExpressionKind::Unsafe(block, _)
+            | ExpressionKind::Comptime(block, _) => self.rewrite_block(block),
+            ExpressionKind::If(if_expression) => {
+                self.rewrite_tail(&mut if_expression.consequence);
+                if let Some(alternative) = &mut if_expression.alternative {
+                    self.rewrite_tail(alternative);
+                }
+            }
+            ExpressionKind::Parenthesized(expression) => self.rewrite_tail(expression),
+            ExpressionKind::Tuple(elements) if elements.len() == self.arity => {
+                let elements = std::mem::take(elements);
+                *expression = self.constructor(elements);
+            }
+            _ => {
+                let tuple = std::mem::replace(
+                    expression,
+                    Expression::new(ExpressionKind::Error, Span::default()),
+                );
+                *expression = self.destructure(tuple);
+            }
+        }
+    }
+
+    /// Returns `{ let ($tuple_0, $tuple_1, ...) = tuple; Name { _0: $tuple_0, ... } }`.
+    fn destructure(&self, tuple: Expression) -> Expression {
+        let variables: Vec<_> =
+            (0..self.arity).map(|index| Ident::from(format!("$tuple_{index}"))).collect();
+
+        let pattern = Pattern::Tuple(
+            variables.iter().cloned().map(Pattern::Identifier).collect(),
+            Span::default(),
+        );
+        let let_statement = LetStatement {
+            pattern,
+            r#type: UnresolvedType { typ: UnresolvedTypeData::Unspecified, span: Span::default() },
+            expression: tuple,
+            attributes: Vec::new(),
+            comptime: false,
+        };
+
+        let elements = variables
+            .into_iter()
+            .map(|variable| {
+                Expression::new(
+                    ExpressionKind::Variable(Path::from_ident(variable)),
+                    Span::default(),
+                )
+            })
+            .collect();
+
+        let statements = vec![
+            Statement { kind: StatementKind::Let(let_statement), span: Span::default() },
+            Statement {
+                kind: StatementKind::Expression(self.constructor(elements)),
+                span: Span::default(),
+            },
+        ];
+        Expression::new(ExpressionKind::Block(BlockExpression { statements }), Span::default())
+    }
+
+    fn constructor(&self, elements: Vec<Expression>) -> Expression {
+        let path = Path::from_ident(self.struct_name.clone());
+        let typ = UnresolvedTypeData::Named(path, GenericTypeArgs::default(), false);
+        let constructor = ConstructorExpression {
+            typ: UnresolvedType { typ, span: Span::default() },
+            fields: elements
+                .into_iter()
+                .enumerate()
+                .map(|(index, element)| (field_name(index), element))
+                .collect(),
+            struct_type: None,
+        };
+        Expression::new(ExpressionKind::Constructor(Box::new(constructor)), Span::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+
+    use crate::{
+        ast::NoirFunction,
+        parser::{parse_program, ItemKind},
+    };
+
+    use super::tupleize_to_struct;
+
+    fn parse_function(src: &str) -> NoirFunction {
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        match module.items.remove(0).kind {
+            ItemKind::Function(function) => function,
+            _ => panic!("Expected a function"),
+        }
+    }
+
+    fn body_strings(function: &NoirFunction) -> Vec<String> {
+        vecmap(&function.def.body.statements, |statement| statement.kind.to_string())
+    }
+
+    #[test]
+    fn converts_pair_return() {
+        let mut function = parse_function(
+            "
+            fn pair<T, U>(x: T, y: U) -> (T, bool) {
+                if foo(y) { (x, true) } else { bar(x) }
+            }
+            ",
+        );
+
+        let noir_struct = tupleize_to_struct(&mut function, "Pair").unwrap();
+
+        assert_eq!(noir_struct.name.to_string(), "Pair");
+        assert_eq!(vecmap(&noir_struct.generics, ToString::to_string), vec!["T"]);
+        let fields =
+            vecmap(&noir_struct.fields, |field| format!("{}: {}", field.item.name, field.item.typ));
+        assert_eq!(fields, vec!["_0: T", "_1: bool"]);
+
+        assert_eq!(function.def.return_type.to_string(), "Pair<T>");
+        assert_eq!(
+            body_strings(&function),
+            vec![
+                "if foo(y) {\n    (Pair { _0: x, _1: true })\n} else {\n    {\n        let ($tuple_0, $tuple_1) = bar(x)\n        (Pair { _0: $tuple_0, _1: $tuple_1 })\n    }\n}"
+            ]
+        );
+    }
+
+    #[test]
+    fn converts_only_the_outermost_tuple() {
+        let mut function = parse_function("fn nested() -> (Field, (u8, bool)) { (1, (2, true)) }");
+
+        let noir_struct = tupleize_to_struct(&mut function, "Nested").unwrap();
+
+        assert!(noir_struct.generics.is_empty());
+        let fields =
+            vecmap(&noir_struct.fields, |field| format!("{}: {}", field.item.name, field.item.typ));
+        assert_eq!(fields, vec!["_0: Field", "_1: (u8, bool)"]);
+
+        assert_eq!(function.def.return_type.to_string(), "Nested");
+        assert_eq!(body_strings(&function), vec!["(Nested { _0: 1, _1: (2, true) })"]);
+    }
+
+    #[test]
+    fn leaves_non_tuple_returns_untouched() {
+        let mut function = parse_function("fn single() -> Field { 1 }");
+        assert!(tupleize_to_struct(&mut function, "Single").is_none());
+        assert_eq!(body_strings(&function), vec!["1"]);
+    }
+}
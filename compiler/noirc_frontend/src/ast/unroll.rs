@@ -0,0 +1,260 @@
+use std::ops::Range;
+
+use acvm::{AcirField, FieldElement};
+use noirc_errors::Span;
+
+use crate::{
+    ast::{
+        inline_global::VariableSubstituter,
+        scopes::{walk_expression, walk_module, ScopedRewriter},
+        BlockExpression, Expression, ExpressionKind, ForBounds, ForLoopStatement, ForRange,
+        Literal, Statement, StatementKind, Visitor,
+    },
+    ParsedModule,
+};
+
+/// Replaces every `for` loop of `module` whose bounds are both non-negative integer literals, as
+/// in `for i in 0..3 { ... }` or `for i in 0..=2 { ... }`, with a block containing one copy of the
+/// loop body per iteration. In each copy the loop index is replaced by the iteration's value,
+/// except where a local variable of the same name shadows it.
+///
+/// Nested loops are unrolled from the inside out, so an inner loop whose bounds depend on an outer
+/// loop's index is unrolled too. Loops with other bounds, loops with more than
+/// [MAX_UNROLL_ITERATIONS] iterations, loops which would be unrolled into more than
+/// [MAX_UNROLLED_STATEMENTS] statements (counting those of the loops nested in them), and loops
+/// whose body contains a `break` or `continue` of their own, are left as is.
+pub fn unroll_constant_for_loops(module: &mut ParsedModule) {
+    walk_module(module, &mut LoopUnroller { budget: MAX_UNROLLED_STATEMENTS });
+}
+
+struct LoopUnroller {
+    /// The maximum number of statements a loop may be unrolled into.
+    budget: usize,
+}
+
+impl ScopedRewriter for LoopUnroller {
+    fn exit_statement(&mut self, statement: &mut Statement) {
+        let StatementKind::For(for_loop) = &statement.kind else {
+            return;
+        };
+        let Some(indices) = constant_range(&for_loop.range) else {
+            return;
+        };
+        if has_own_break_or_continue(&for_loop.block) {
+            return;
+        }
+
+        // Each copy of the body is a statement of its own. The body's nested loops have already
+        // been unrolled, so their statements are counted too.
+        let iterations = (indices.end - indices.start) as usize;
+        let statements_per_copy = 1 + statement_count(&for_loop.block);
+        if iterations.saturating_mul(statements_per_copy) > self.budget {
+            return;
+        }
+        // Split the budget between the copies, for the loops which can only be unrolled once the
+        // index is substituted
+        let budget = self.budget / iterations.max(1);
+
+        let statements = indices
+            .map(|index| {
+                let mut body = for_loop.block.clone();
+                let index = Expression::new(
+                    ExpressionKind::integer(FieldElement::from(index)),
+                    Span::default(),
+                );
+                let name = &for_loop.identifier.0.contents;
+                walk_expression(&mut body, &mut VariableSubstituter::new(name, index, false));
+
+                // Loops whose bounds depended on the index can now be unrolled too
+                walk_expression(&mut body, &mut LoopUnroller { budget });
+
+                let span = body.span;
+                Statement { kind: StatementKind::Expression(body), span }
+            })
+            .collect();
+
+        let block = ExpressionKind::Block(BlockExpression { statements });
+        statement.kind = StatementKind::Expression(Expression::new(block, statement.span));
+    }
+}
+
+/// The maximum number of iterations of a loop which is unrolled, so that the body of a long loop
+/// isn't copied an unbounded number of times.
+pub const MAX_UNROLL_ITERATIONS: u128 = 1024;
+
+/// The maximum number of statements, including nested ones, produced by unrolling a loop, so that
+/// unrolling nested loops doesn't multiply the size of their body without bound.
+pub const MAX_UNROLLED_STATEMENTS: usize = 1 << 16;
+
+/// Returns the values taken by the index of a loop over `range`, if its bounds are constant and
+/// it has at most [MAX_UNROLL_ITERATIONS] iterations.
+fn constant_range(range: &ForRange) -> Option<Range<u128>> {
+    let ForRange::Range(ForBounds { start, end, inclusive }) = range else {
+        return None;
+    };
+
+    let constant = |bound: &Expression| match &bound.kind {
+        ExpressionKind::Literal(Literal::Integer(value, false)) => value.try_into_u128(),
+        _ => None,
+    };
+    let (start, end) = (constant(start)?, constant(end)?);
+
+    let end = if *inclusive { end.checked_add(1)? } else { end };
+    let end = end.max(start);
+    (end - start <= MAX_UNROLL_ITERATIONS).then_some(start..end)
+}
+
+/// Returns the number of statements in `body`, including those in nested blocks.
+fn statement_count(body: &Expression) -> usize {
+    let mut counter = StatementCounter { count: 0 };
+    body.accept(&mut counter);
+    counter.count
+}
+
+struct StatementCounter {
+    count: usize,
+}
+
+impl Visitor for StatementCounter {
+    fn visit_statement(&mut self, _: &Statement) -> bool {
+        self.count += 1;
+        true
+    }
+}
+
+fn has_own_break_or_continue(body: &Expression) -> bool {
+    let mut finder = BreakOrContinueFinder { found: false };
+    body.accept(&mut finder);
+    finder.found
+}
+
+struct BreakOrContinueFinder {
+    found: bool,
+}
+
+impl Visitor for BreakOrContinueFinder {
+    fn visit_for_loop_statement(&mut self, _: &ForLoopStatement) -> bool {
+        // A `break` or `continue` in a nested loop applies to that loop
+        false
+    }
+
+    fn visit_break(&mut self) {
+        self.found = true;
+    }
+
+    fn visit_continue(&mut self) {
+        self.found = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iter_extended::vecmap;
+
+    use crate::{
+        parser::{parse_program, ItemKind},
+        ParsedModule,
+    };
+
+    use super::unroll_constant_for_loops;
+
+    fn unrolled_body(src: &str) -> Vec<String> {
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+
+        unroll_constant_for_loops(&mut module);
+        body_strings(&module)
+    }
+
+    fn body_strings(module: &ParsedModule) -> Vec<String> {
+        let ItemKind::Function(function) = &module.items[0].kind else {
+            panic!("Expected a function");
+        };
+        vecmap(&function.def.body.statements, |statement| statement.kind.to_string())
+    }
+
+    #[test]
+    fn unrolls_empty_range() {
+        let body = unrolled_body("fn main() { for i in 3..3 { foo(i); } }");
+        assert_eq!(body, vec!["{\n}"]);
+    }
+
+    #[test]
+    fn unrolls_single_iteration_range() {
+        let body = unrolled_body("fn main() { for i in 0..1 { foo(i); } }");
+        assert_eq!(body, vec!["{\n    {\n        foo(0);\n    }\n}"]);
+    }
+
+    #[test]
+    fn unrolls_multiple_iteration_range() {
+        let body =
+            unrolled_body("fn main() { for i in 1..=3 { foo(i, bar::i); let i = 0; foo(i); } }");
+        let copies = (1..=3).map(|index| {
+            format!("    {{\n        foo({index}, bar::i);\n        let i = 0\n        foo(i);\n    }}\n")
+        });
+        assert_eq!(body, vec![format!("{{\n{}}}", copies.collect::<String>())]);
+    }
+
+    #[test]
+    fn unrolls_loops_bounded_by_an_outer_index() {
+        let body = unrolled_body("fn main() { for i in 1..2 { for j in 0..i { foo(j); } } }");
+        assert_eq!(body, vec!["{\n    {\n        {\n            {\n                foo(0);\n            }\n        }\n    }\n}"]);
+    }
+
+    #[test]
+    fn leaves_long_loops_untouched() {
+        let src = "
+        fn main() {
+            for i in 0..4294967295 { foo(i); }
+            for i in 0..=1024 { foo(i); }
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        let expected = body_strings(&module);
+
+        unroll_constant_for_loops(&mut module);
+        assert_eq!(body_strings(&module), expected);
+
+        let body = unrolled_body("fn main() { for i in 0..1024 { foo(i); } }");
+        assert_eq!(body[0].matches("foo(").count(), 1024);
+    }
+
+    #[test]
+    fn leaves_nested_loops_over_the_budget_rolled() {
+        let body = unrolled_body(
+            "fn main() { for i in 0..1024 { for j in 0..1024 { for k in 0..1024 { f(); } } } }",
+        );
+        // Only the innermost loop is unrolled, each copy of the others would be too large
+        assert_eq!(body.len(), 1);
+        assert!(body[0].starts_with("for i in 0..1024"));
+        assert!(body[0].contains("for j in 0..1024"));
+        assert!(!body[0].contains("for k in"));
+        assert_eq!(body[0].matches("f()").count(), 1024);
+
+        let body = unrolled_body("fn main() { for i in 0..4 { for j in 0..4 { f(i, j); } } }");
+        assert_eq!(body[0].matches("f(").count(), 16);
+
+        // Loops bounded by an outer index share the outer loop's budget, so only the short ones
+        // are unrolled
+        let body = unrolled_body("fn main() { for i in 0..1024 { for j in 0..i { f(j); } } }");
+        assert!(!body[0].contains("for j in 0..1 {"));
+        assert!(body[0].contains("for j in 0..1023"));
+    }
+
+    #[test]
+    fn leaves_other_loops_untouched() {
+        let src = "
+        fn main(n: u32) {
+            for i in 0..n { foo(i); }
+            for i in 0..2 { if i == 1 { break; } }
+        }
+        ";
+        let (mut module, errors) = parse_program(src);
+        assert!(errors.is_empty());
+        let expected = body_strings(&module);
+
+        unroll_constant_for_loops(&mut module);
+        assert_eq!(body_strings(&module), expected);
+    }
+}
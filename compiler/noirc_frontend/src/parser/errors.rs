@@ -75,6 +75,8 @@ pub enum ParserErrorReason {
     ComptimeDeprecated,
     #[error("{0} are experimental and aren't fully supported yet")]
     ExperimentalFeature(&'static str),
+    #[error("{0} are not supported")]
+    UnsupportedFeature(&'static str),
     #[error(
         "Multiple primary attributes found. Only one function attribute is allowed per function"
     )]
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use noirc_errors::Span;
+use thiserror::Error;
+
+use crate::ast::{Ident, Pattern};
+
+use super::{Item, ItemKind, ParsedModule};
+
+/// The error returned by [merge_modules] when both modules define items with the same name.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("Cannot merge modules, found duplicate definitions of: {}", self.names().join(", "))]
+pub struct MergeConflict {
+    pub duplicates: Vec<DuplicateDefinition>,
+}
+
+impl MergeConflict {
+    fn names(&self) -> Vec<&str> {
+        self.duplicates.iter().map(|duplicate| duplicate.name.as_str()).collect()
+    }
+}
+
+/// A top-level item of the second module with the same name as one in the first module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateDefinition {
+    pub name: String,
+    /// The span of the name in the first module.
+    pub first_span: Span,
+    /// The span of the name in the second module.
+    pub second_span: Span,
+}
+
+/// The namespaces top-level items are defined in: two items only conflict if they share a name
+/// within the same namespace.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Namespace {
+    /// Functions and globals.
+    Value,
+    /// Structs, traits, type aliases and modules.
+    Type,
+}
+
+/// Concatenates the items of `a` and `b` into a single module.
+///
+/// If both modules define a function, global, struct, trait, type alias or submodule with the
+/// same name, no module is returned and every such name is reported in a [MergeConflict] instead.
+/// Imports, impls and trait impls never conflict.
+pub fn merge_modules(a: ParsedModule, b: ParsedModule) -> Result<ParsedModule, MergeConflict> {
+    let definitions: HashMap<_, _> = a
+        .items
+        .iter()
+        .filter_map(item_definition)
+        .map(|(namespace, name)| ((namespace, name.0.contents.as_str()), name.span()))
+        .collect();
+
+    let duplicates: Vec<_> = b
+        .items
+        .iter()
+        .filter_map(item_definition)
+        .filter_map(|(namespace, name)| {
+            let first_span = definitions.get(&(namespace, name.0.contents.as_str()))?;
+            Some(DuplicateDefinition {
+                name: name.to_string(),
+                first_span: *first_span,
+                second_span: name.span(),
+            })
+        })
+        .collect();
+
+    if !duplicates.is_empty() {
+        return Err(MergeConflict { duplicates });
+    }
+
+    let mut merged = a;
+    merged.items.extend(b.items);
+    merged.inner_doc_comments.extend(b.inner_doc_comments);
+    Ok(merged)
+}
+
+/// Returns the namespace and name of the definition introduced by `item`, if any.
+fn item_definition(item: &Item) -> Option<(Namespace, &Ident)> {
+    match &item.kind {
+        ItemKind::Function(function) => Some((Namespace::Value, &function.def.name)),
+        ItemKind::Global(let_statement, _) => match &let_statement.pattern {
+            Pattern::Identifier(name) => Some((Namespace::Value, name)),
+            _ => None,
+        },
+        ItemKind::Struct(noir_struct) => Some((Namespace::Type, &noir_struct.name)),
+        ItemKind::Trait(noir_trait) => Some((Namespace::Type, &noir_trait.name)),
+        ItemKind::TypeAlias(type_alias) => Some((Namespace::Type, &type_alias.name)),
+        ItemKind::ModuleDecl(module_declaration) => {
+            Some((Namespace::Type, &module_declaration.ident))
+        }
+        ItemKind::Submodules(submodule) => Some((Namespace::Type, &submodule.name)),
+        ItemKind::Import(..)
+        | ItemKind::TraitImpl(_)
+        | ItemKind::Impl(_)
+        | ItemKind::InnerAttribute(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::parse_program;
+
+    use super::merge_modules;
+
+    #[test]
+    fn merges_modules_without_conflicts() {
+        let (a, errors) = parse_program("fn foo() {} struct Foo {} impl Foo {}");
+        assert!(errors.is_empty());
+        let (b, errors) = parse_program("global foo2: Field = 1; struct Bar {} impl Foo {}");
+        assert!(errors.is_empty());
+
+        let merged = merge_modules(a, b).unwrap();
+        assert_eq!(merged.items.len(), 6);
+    }
+
+    #[test]
+    fn reports_duplicate_definitions() {
+        let a_src = "fn foo() {} struct Foo {} mod bar {}";
+        let b_src = "fn bar() {} global foo: Field = 1; trait Foo {} mod bar;";
+        let (a, errors) = parse_program(a_src);
+        assert!(errors.is_empty());
+        let (b, errors) = parse_program(b_src);
+        assert!(errors.is_empty());
+
+        let conflict = merge_modules(a, b).unwrap_err();
+        let duplicates = conflict.duplicates;
+        assert_eq!(duplicates.len(), 3);
+
+        assert_eq!(duplicates[0].name, "foo");
+        assert_eq!(duplicates[0].first_span.start() as usize, a_src.find("foo").unwrap());
+        assert_eq!(duplicates[0].second_span.start() as usize, b_src.find("foo").unwrap());
+
+        assert_eq!(duplicates[1].name, "Foo");
+        assert_eq!(duplicates[2].name, "bar");
+        assert_eq!(duplicates[2].second_span.start() as usize, b_src.rfind("bar").unwrap());
+    }
+}
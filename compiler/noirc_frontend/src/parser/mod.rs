@@ -8,8 +8,10 @@
 //! see parser.rs. The definition of the abstract syntax tree can be found in the `ast` folder.
 mod errors;
 mod labels;
+mod merge;
 #[allow(clippy::module_inception)]
 mod parser;
+mod restricted;
 
 use crate::ast::{
     Documented, Ident, ImportStatement, ItemVisibility, LetStatement, ModuleDeclaration,
@@ -19,8 +21,10 @@ use crate::token::SecondaryAttribute;
 
 pub use errors::ParserError;
 pub use errors::ParserErrorReason;
+pub use merge::{merge_modules, DuplicateDefinition, MergeConflict};
 use noirc_errors::Span;
 pub use parser::{parse_program, Parser, StatementOrExpressionOrLValue};
+pub use restricted::{parse_program_restricted, FeatureSet};
 
 #[derive(Clone, Default)]
 pub struct SortedModule {
@@ -0,0 +1,199 @@
+use std::ops::BitOr;
+
+use noirc_errors::Span;
+
+use crate::ast::{Expression, ExpressionKind, Literal, Statement, StatementKind, Visitor};
+
+use super::{parse_program, ParsedModule, ParserError, ParserErrorReason};
+
+/// A set of optional language features, used to restrict which expressions and statements
+/// [parse_program_restricted] accepts.
+///
+/// Features are combined with `|`, e.g. `FeatureSet::LAMBDAS | FeatureSet::LOOPS`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FeatureSet(u32);
+
+impl FeatureSet {
+    /// `comptime` blocks and statements.
+    pub const COMPTIME: FeatureSet = FeatureSet(1 << 0);
+    /// `unsafe` blocks.
+    pub const UNSAFE: FeatureSet = FeatureSet(1 << 1);
+    /// `quote { ... }` expressions and `$` unquoting.
+    pub const QUOTING: FeatureSet = FeatureSet(1 << 2);
+    /// Lambda expressions.
+    pub const LAMBDAS: FeatureSet = FeatureSet(1 << 3);
+    /// `for` loops, including `break` and `continue`.
+    pub const LOOPS: FeatureSet = FeatureSet(1 << 4);
+    /// Assignments to mutable variables.
+    pub const ASSIGNMENTS: FeatureSet = FeatureSet(1 << 5);
+    /// Slice literals (`&[...]`).
+    pub const SLICES: FeatureSet = FeatureSet(1 << 6);
+    /// Format string literals (`f"..."`).
+    pub const FORMAT_STRINGS: FeatureSet = FeatureSet(1 << 7);
+    /// Paths through a primitive type or a trait, e.g. `u32::max` or `<T as Trait>::f`.
+    pub const TYPE_PATHS: FeatureSet = FeatureSet(1 << 8);
+
+    const FEATURE_NAMES: [(FeatureSet, &'static str); 9] = [
+        (FeatureSet::COMPTIME, "comptime blocks"),
+        (FeatureSet::UNSAFE, "unsafe blocks"),
+        (FeatureSet::QUOTING, "quoted expressions"),
+        (FeatureSet::LAMBDAS, "lambdas"),
+        (FeatureSet::LOOPS, "loops"),
+        (FeatureSet::ASSIGNMENTS, "assignments"),
+        (FeatureSet::SLICES, "slice literals"),
+        (FeatureSet::FORMAT_STRINGS, "format strings"),
+        (FeatureSet::TYPE_PATHS, "type and trait paths"),
+    ];
+
+    /// The set containing no optional features.
+    pub const fn empty() -> FeatureSet {
+        FeatureSet(0)
+    }
+
+    /// The set containing every optional feature.
+    pub const fn all() -> FeatureSet {
+        FeatureSet((1 << FeatureSet::FEATURE_NAMES.len()) - 1)
+    }
+
+    /// Returns `true` if every feature in `other` is also in `self`.
+    pub const fn contains(self, other: FeatureSet) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    fn name(self) -> &'static str {
+        let feature = FeatureSet::FEATURE_NAMES.iter().find(|(feature, _)| *feature == self);
+        feature.expect("Expected a single feature").1
+    }
+}
+
+impl BitOr for FeatureSet {
+    type Output = FeatureSet;
+
+    fn bitor(self, rhs: FeatureSet) -> FeatureSet {
+        FeatureSet(self.0 | rhs.0)
+    }
+}
+
+/// Parses `source_program` like [parse_program], additionally returning an
+/// [UnsupportedFeature][ParserErrorReason::UnsupportedFeature] error for every expression or
+/// statement which uses a feature that isn't in `allowed`.
+///
+/// Features which aren't optional (such as literals, blocks, calls or `let` statements) are
+/// always accepted.
+pub fn parse_program_restricted(
+    source_program: &str,
+    allowed: FeatureSet,
+) -> (ParsedModule, Vec<ParserError>) {
+    let (program, mut errors) = parse_program(source_program);
+
+    let mut checker = FeatureChecker { allowed, errors: Vec::new() };
+    program.accept(&mut checker);
+    errors.extend(checker.errors);
+
+    (program, errors)
+}
+
+struct FeatureChecker {
+    allowed: FeatureSet,
+    errors: Vec<ParserError>,
+}
+
+impl FeatureChecker {
+    fn check(&mut self, feature: Option<FeatureSet>, span: Span) {
+        if let Some(feature) = feature {
+            if !self.allowed.contains(feature) {
+                let reason = ParserErrorReason::UnsupportedFeature(feature.name());
+                self.errors.push(ParserError::with_reason(reason, span));
+            }
+        }
+    }
+}
+
+impl Visitor for FeatureChecker {
+    fn visit_expression(&mut self, expression: &Expression) -> bool {
+        let feature = match &expression.kind {
+            ExpressionKind::Comptime(..) => Some(FeatureSet::COMPTIME),
+            ExpressionKind::Unsafe(..) => Some(FeatureSet::UNSAFE),
+            ExpressionKind::Quote(_) | ExpressionKind::Unquote(_) => Some(FeatureSet::QUOTING),
+            ExpressionKind::Lambda(_) => Some(FeatureSet::LAMBDAS),
+            ExpressionKind::AsTraitPath(_) | ExpressionKind::TypePath(_) => {
+                Some(FeatureSet::TYPE_PATHS)
+            }
+            ExpressionKind::Literal(Literal::Slice(_)) => Some(FeatureSet::SLICES),
+            ExpressionKind::Literal(Literal::FmtStr(_)) => Some(FeatureSet::FORMAT_STRINGS),
+            _ => None,
+        };
+        self.check(feature, expression.span);
+        true
+    }
+
+    fn visit_statement(&mut self, statement: &Statement) -> bool {
+        let feature = match &statement.kind {
+            StatementKind::Comptime(_) => Some(FeatureSet::COMPTIME),
+            StatementKind::For(_) | StatementKind::Break | StatementKind::Continue => {
+                Some(FeatureSet::LOOPS)
+            }
+            StatementKind::Assign(_) => Some(FeatureSet::ASSIGNMENTS),
+            _ => None,
+        };
+        self.check(feature, statement.span);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parser::{ParserError, ParserErrorReason};
+
+    use super::{parse_program_restricted, FeatureSet};
+
+    fn unsupported_features(errors: &[ParserError]) -> Vec<&'static str> {
+        errors
+            .iter()
+            .map(|error| match error.reason() {
+                Some(ParserErrorReason::UnsupportedFeature(feature)) => *feature,
+                _ => panic!("Expected an unsupported feature error, got {error:?}"),
+            })
+            .collect()
+    }
+
+    const SRC: &str = "
+    fn main(x: Field) -> pub Field {
+        let mut y = 0;
+        for i in 0..3 {
+            y = y + i;
+        }
+        let f = |z: Field| z + x;
+        comptime {
+            let _ = 1;
+        }
+        unsafe { f(y) }
+    }
+    ";
+
+    #[test]
+    fn accepts_allowed_features() {
+        let allowed = FeatureSet::LOOPS
+            | FeatureSet::ASSIGNMENTS
+            | FeatureSet::LAMBDAS
+            | FeatureSet::COMPTIME
+            | FeatureSet::UNSAFE;
+        let (_, errors) = parse_program_restricted(SRC, allowed);
+        assert!(errors.is_empty());
+
+        let (_, errors) = parse_program_restricted(SRC, FeatureSet::all());
+        assert!(errors.is_empty());
+    }
+
+    #[test]
+    fn rejects_unsupported_features() {
+        let (_, errors) = parse_program_restricted(SRC, FeatureSet::LOOPS | FeatureSet::UNSAFE);
+        assert_eq!(
+            unsupported_features(&errors),
+            vec!["assignments", "lambdas", "comptime blocks"]
+        );
+
+        let (_, errors) = parse_program_restricted(SRC, FeatureSet::empty());
+        assert_eq!(errors.len(), 5);
+    }
+}
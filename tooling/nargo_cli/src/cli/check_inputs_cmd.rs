@@ -0,0 +1,213 @@
+use clap::Args;
+use nargo::constants::{PROVER_INPUT_FILE, VERIFIER_INPUT_FILE};
+use nargo::package::CrateName;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_abi::input_parser::{Format, InputValue};
+use noirc_abi::{Abi, InputMap, MAIN_RETURN_NAME};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+
+use super::compile_cmd::compile_workspace_full;
+use super::fs::{inputs::read_inputs_from_file, program::read_program_from_file};
+use super::NargoConfig;
+use crate::errors::CliError;
+
+/// Checks that the public inputs in the prover and verifier toml files agree
+#[derive(Debug, Clone, Args)]
+pub(crate) struct CheckInputsCommand {
+    /// The name of the toml file which contains the inputs for the prover
+    #[clap(long, short, default_value = PROVER_INPUT_FILE)]
+    prover_name: String,
+
+    /// The name of the toml file which contains the inputs for the verifier
+    #[clap(long, short, default_value = VERIFIER_INPUT_FILE)]
+    verifier_name: String,
+
+    /// The name of the package to check
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Check all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(args: CheckInputsCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    // Compile the full workspace in order to generate the ABIs of its binary packages.
+    compile_workspace_full(&workspace, &args.compile_options)?;
+
+    let mut inconsistent_packages = 0;
+    let binary_packages = workspace.into_iter().filter(|package| package.is_binary());
+    for package in binary_packages {
+        let program_artifact_path = workspace.package_build_path(package);
+        let abi = read_program_from_file(program_artifact_path)?.abi;
+
+        let (prover_inputs, prover_return_value) =
+            read_inputs_from_file(&package.root_dir, &args.prover_name, Format::Toml, &abi, None)?;
+        let (verifier_inputs, verifier_return_value) = read_inputs_from_file(
+            &package.root_dir,
+            &args.verifier_name,
+            Format::Toml,
+            &abi.clone().public_abi(),
+            None,
+        )?;
+
+        let mismatches = find_public_input_mismatches(
+            &abi,
+            (&prover_inputs, prover_return_value.as_ref()),
+            (&verifier_inputs, verifier_return_value.as_ref()),
+        );
+
+        if mismatches.is_empty() {
+            println!(
+                "[{}] Public inputs in {}.toml and {}.toml are consistent",
+                package.name, args.prover_name, args.verifier_name
+            );
+        } else {
+            inconsistent_packages += 1;
+            for mismatch in mismatches {
+                eprintln!(
+                    "[{}] Public input `{}` differs: {:?} in {}.toml but {:?} in {}.toml",
+                    package.name,
+                    mismatch.name,
+                    mismatch.prover_value,
+                    args.prover_name,
+                    mismatch.verifier_value,
+                    args.verifier_name,
+                );
+            }
+        }
+    }
+
+    if inconsistent_packages == 0 {
+        Ok(())
+    } else {
+        Err(CliError::Generic(format!(
+            "{inconsistent_packages} package(s) have inconsistent public inputs"
+        )))
+    }
+}
+
+/// A public input whose value differs between the prover and verifier inputs.
+#[derive(Debug, PartialEq)]
+struct PublicInputMismatch {
+    name: String,
+    prover_value: InputValue,
+    verifier_value: InputValue,
+}
+
+/// Compares the value of each public parameter of `abi` in the prover and verifier inputs.
+///
+/// Providing the return value is optional for both files, so it is only compared if present in both.
+fn find_public_input_mismatches(
+    abi: &Abi,
+    (prover_inputs, prover_return_value): (&InputMap, Option<&InputValue>),
+    (verifier_inputs, verifier_return_value): (&InputMap, Option<&InputValue>),
+) -> Vec<PublicInputMismatch> {
+    let public_parameters = abi.parameters.iter().filter(|param| param.is_public());
+    let public_values = public_parameters
+        .map(|param| {
+            (param.name.as_str(), prover_inputs.get(&param.name), verifier_inputs.get(&param.name))
+        })
+        .chain(std::iter::once((MAIN_RETURN_NAME, prover_return_value, verifier_return_value)));
+
+    public_values
+        .filter_map(|(name, prover_value, verifier_value)| match (prover_value, verifier_value) {
+            (Some(prover_value), Some(verifier_value)) if prover_value != verifier_value => {
+                Some(PublicInputMismatch {
+                    name: name.to_owned(),
+                    prover_value: prover_value.clone(),
+                    verifier_value: verifier_value.clone(),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use acvm::FieldElement;
+    use noirc_abi::{
+        input_parser::InputValue, Abi, AbiParameter, AbiReturnType, AbiType, AbiVisibility,
+        MAIN_RETURN_NAME,
+    };
+
+    use super::{find_public_input_mismatches, PublicInputMismatch};
+
+    fn field(value: u128) -> InputValue {
+        InputValue::Field(FieldElement::from(value))
+    }
+
+    #[test]
+    fn reports_only_differing_public_inputs() {
+        let param = |name: &str, visibility| AbiParameter {
+            name: name.to_string(),
+            typ: AbiType::Field,
+            visibility,
+        };
+        let abi = Abi {
+            parameters: vec![
+                param("x", AbiVisibility::Private),
+                param("y", AbiVisibility::Public),
+                param("z", AbiVisibility::Public),
+            ],
+            return_type: Some(AbiReturnType {
+                abi_type: AbiType::Field,
+                visibility: AbiVisibility::Public,
+            }),
+            error_types: BTreeMap::new(),
+        };
+
+        let prover_inputs = BTreeMap::from([
+            ("x".to_string(), field(1)),
+            ("y".to_string(), field(2)),
+            ("z".to_string(), field(3)),
+        ]);
+        let verifier_inputs =
+            BTreeMap::from([("y".to_string(), field(2)), ("z".to_string(), field(4))]);
+
+        let mismatches = find_public_input_mismatches(
+            &abi,
+            (&prover_inputs, Some(&field(5))),
+            (&verifier_inputs, Some(&field(6))),
+        );
+        assert_eq!(
+            mismatches,
+            vec![
+                PublicInputMismatch {
+                    name: "z".to_string(),
+                    prover_value: field(3),
+                    verifier_value: field(4),
+                },
+                PublicInputMismatch {
+                    name: MAIN_RETURN_NAME.to_string(),
+                    prover_value: field(5),
+                    verifier_value: field(6),
+                },
+            ]
+        );
+
+        // The return value is not compared if either file omits it.
+        let mismatches = find_public_input_mismatches(
+            &abi,
+            (&prover_inputs, None),
+            (&verifier_inputs, Some(&field(6))),
+        );
+        assert_eq!(mismatches.len(), 1);
+    }
+}
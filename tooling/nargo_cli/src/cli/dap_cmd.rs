@@ -121,11 +121,16 @@ fn load_and_compile_project(
 
     let compiled_program = nargo::ops::transform_program(compiled_program, expression_width);
 
-    let (inputs_map, _) =
-        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &compiled_program.abi)
-            .map_err(|_| {
-                LoadError::Generic(format!("Failed to read program inputs from {}", prover_name))
-            })?;
+    let (inputs_map, _) = read_inputs_from_file(
+        &package.root_dir,
+        prover_name,
+        Format::Toml,
+        &compiled_program.abi,
+        None,
+    )
+    .map_err(|_| {
+        LoadError::Generic(format!("Failed to read program inputs from {}", prover_name))
+    })?;
     let initial_witness = compiled_program
         .abi
         .encode(&inputs_map, None)
@@ -209,7 +209,7 @@ fn debug_program_and_decode(
 ) -> Result<(Option<InputValue>, Option<WitnessStack<FieldElement>>), CliError> {
     // Parse the initial witness values from Prover.toml
     let (inputs_map, _) =
-        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
+        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi, None)?;
     let program_abi = program.abi.clone();
     let witness_stack = debug_program(program, &inputs_map)?;
 
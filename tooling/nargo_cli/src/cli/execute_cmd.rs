@@ -34,6 +34,11 @@ pub(crate) struct ExecuteCommand {
     #[clap(long, short, default_value = PROVER_INPUT_FILE)]
     prover_name: String,
 
+    /// Accept decimal strings such as "1.5" in the prover toml file as fixed-point values with this
+    /// many fractional bits, i.e. multiplied by 2^BITS
+    #[clap(long, value_name = "BITS")]
+    fixed_point_scale: Option<u32>,
+
     /// The name of the package to execute
     #[clap(long, conflicts_with = "workspace")]
     package: Option<CrateName>,
@@ -75,6 +80,7 @@ pub(crate) fn run(args: ExecuteCommand, config: NargoConfig) -> Result<(), CliEr
             program,
             package,
             &args.prover_name,
+            args.fixed_point_scale,
             args.oracle_resolver.as_deref(),
             Some(workspace.root_dir.clone()),
             Some(package.name.to_string()),
@@ -97,13 +103,19 @@ fn execute_program_and_decode(
     program: CompiledProgram,
     package: &Package,
     prover_name: &str,
+    fixed_point_scale: Option<u32>,
     foreign_call_resolver_url: Option<&str>,
     root_path: Option<PathBuf>,
     package_name: Option<String>,
 ) -> Result<(Option<InputValue>, WitnessStack<FieldElement>), CliError> {
     // Parse the initial witness values from Prover.toml
-    let (inputs_map, _) =
-        read_inputs_from_file(&package.root_dir, prover_name, Format::Toml, &program.abi)?;
+    let (inputs_map, _) = read_inputs_from_file(
+        &package.root_dir,
+        prover_name,
+        Format::Toml,
+        &program.abi,
+        fixed_point_scale,
+    )?;
     let witness_stack =
         execute_program(&program, &inputs_map, foreign_call_resolver_url, root_path, package_name)?;
     // Get the entry point witness for the ABI
@@ -7,17 +7,21 @@ use std::{collections::BTreeMap, path::Path};
 use crate::errors::FilesystemError;
 
 /// Returns the circuit's parameters and its return value, if one exists.
+///
+/// If `fixed_point_scale` is set, decimal strings such as `"1.5"` are accepted as fixed-point
+/// values with that many fractional bits (see [Format::parse_fixed_point]).
 /// # Examples
 ///
 /// ```ignore
 /// let (input_map, return_value): (InputMap, Option<InputValue>) =
-///   read_inputs_from_file(path, "Verifier", Format::Toml, &abi)?;
+///   read_inputs_from_file(path, "Verifier", Format::Toml, &abi, None)?;
 /// ```
 pub(crate) fn read_inputs_from_file<P: AsRef<Path>>(
     path: P,
     file_name: &str,
     format: Format,
     abi: &Abi,
+    fixed_point_scale: Option<u32>,
 ) -> Result<(InputMap, Option<InputValue>), FilesystemError> {
     if abi.is_empty() {
         return Ok((BTreeMap::new(), None));
@@ -35,7 +39,10 @@ pub(crate) fn read_inputs_from_file<P: AsRef<Path>>(
     }
 
     let input_string = std::fs::read_to_string(file_path).unwrap();
-    let mut input_map = format.parse(&input_string, abi)?;
+    let mut input_map = match fixed_point_scale {
+        Some(scale_bits) => format.parse_fixed_point(&input_string, abi, scale_bits)?,
+        None => format.parse(&input_string, abi)?,
+    };
     let return_value = input_map.remove(MAIN_RETURN_NAME);
 
     Ok((input_map, return_value))
@@ -27,7 +27,9 @@ use super::{
 ///
 /// Current information provided per circuit:
 /// 1. The number of ACIR opcodes
-/// 2. Counts the final number gates in the circuit used by a backend
+/// 2. The number of witnesses, i.e. the highest witness index used by the circuit plus one
+/// 3. The number of public inputs, counting both public parameters and return values
+/// 4. The number of Brillig opcodes of the program's unconstrained functions
 #[derive(Debug, Clone, Args)]
 #[clap(visible_alias = "i")]
 pub(crate) struct InfoCommand {
@@ -115,7 +117,7 @@ pub(crate) fn run(mut args: InfoCommand, config: NargoConfig) -> Result<(), CliE
     } else {
         // Otherwise print human-readable table.
         if !info_report.programs.is_empty() {
-            let mut program_table = table!([Fm->"Package", Fm->"Function", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Brillig Opcodes"]);
+            let mut program_table = table!([Fm->"Package", Fm->"Function", Fm->"Expression Width", Fm->"ACIR Opcodes", Fm->"Witnesses", Fm->"Public Inputs", Fm->"Brillig Opcodes"]);
 
             for program_info in info_report.programs {
                 let program_rows: Vec<Row> = program_info.into();
@@ -154,6 +156,8 @@ impl From<ProgramInfo> for Vec<Row> {
                 Fc->format!("{}", function.name),
                 format!("{:?}", program_info.expression_width),
                 Fc->format!("{}", function.opcodes),
+                Fc->display_optional_count(function.witnesses),
+                Fc->display_optional_count(function.public_inputs),
                 Fc->format!("{}", program_info.unconstrained_functions_opcodes),
             ]
         });
@@ -163,6 +167,8 @@ impl From<ProgramInfo> for Vec<Row> {
                 Fc->format!("{}", function.name),
                 format!("N/A", ),
                 Fc->format!("N/A"),
+                Fc->format!("N/A"),
+                Fc->format!("N/A"),
                 Fc->format!("{}", function.opcodes),
             ]
         }));
@@ -183,6 +189,23 @@ struct ContractInfo {
 struct FunctionInfo {
     name: String,
     opcodes: usize,
+    /// The number of witnesses used by an ACIR function. Not reported for Brillig functions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    witnesses: Option<u32>,
+    /// The number of public inputs of an ACIR function. Not reported for Brillig functions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    public_inputs: Option<usize>,
+}
+
+impl FunctionInfo {
+    /// Returns the info of a function for which only the number of opcodes is known.
+    fn new(name: String, opcodes: usize) -> Self {
+        FunctionInfo { name, opcodes, witnesses: None, public_inputs: None }
+    }
+}
+
+fn display_optional_count<T: std::fmt::Display>(count: Option<T>) -> String {
+    count.map_or_else(|| "N/A".to_string(), |count| count.to_string())
 }
 
 impl From<ContractInfo> for Vec<Row> {
@@ -211,6 +234,8 @@ fn count_opcodes_and_gates_in_program(
         .map(|(i, function)| FunctionInfo {
             name: compiled_program.names[i].clone(),
             opcodes: function.opcodes.len(),
+            witnesses: Some(function.num_vars()),
+            public_inputs: Some(function.public_inputs().0.len()),
         })
         .collect();
 
@@ -231,7 +256,7 @@ fn count_opcodes_and_gates_in_program(
         .clone()
         .iter()
         .zip(opcodes_len)
-        .map(|(name, len)| FunctionInfo { name: name.clone(), opcodes: len })
+        .map(|(name, len)| FunctionInfo::new(name.clone(), len))
         .collect();
 
     ProgramInfo {
@@ -256,6 +281,7 @@ fn profile_brillig_execution(
             prover_name,
             Format::Toml,
             &program_artifact.abi,
+            None,
         )?;
         let initial_witness = program_artifact.abi.encode(&inputs_map, None)?;
 
@@ -271,13 +297,65 @@ fn profile_brillig_execution(
         program_info.push(ProgramInfo {
             package_name: package.name.to_string(),
             expression_width,
-            functions: vec![FunctionInfo { name: "main".to_string(), opcodes: 0 }],
+            functions: vec![FunctionInfo::new("main".to_string(), 0)],
             unconstrained_functions_opcodes: profiling_samples.len(),
-            unconstrained_functions: vec![FunctionInfo {
-                name: "main".to_string(),
-                opcodes: profiling_samples.len(),
-            }],
+            unconstrained_functions: vec![FunctionInfo::new(
+                "main".to_string(),
+                profiling_samples.len(),
+            )],
         });
     }
     Ok(program_info)
 }
+
+#[cfg(test)]
+mod tests {
+    use acvm::acir::circuit::ExpressionWidth;
+    use prettytable::Row;
+    use serde_json::json;
+
+    use super::{FunctionInfo, InfoReport, ProgramInfo};
+
+    fn program_info() -> ProgramInfo {
+        ProgramInfo {
+            package_name: "foo".to_string(),
+            expression_width: ExpressionWidth::Bounded { width: 4 },
+            functions: vec![FunctionInfo {
+                name: "main".to_string(),
+                opcodes: 10,
+                witnesses: Some(12),
+                public_inputs: Some(2),
+            }],
+            unconstrained_functions_opcodes: 7,
+            unconstrained_functions: vec![FunctionInfo::new("helper".to_string(), 7)],
+        }
+    }
+
+    #[test]
+    fn reports_witnesses_and_public_inputs() {
+        let rows: Vec<Row> = program_info().into();
+        let cells: Vec<Vec<String>> =
+            rows.iter().map(|row| row.iter().map(|cell| cell.get_content()).collect()).collect();
+        assert_eq!(
+            cells,
+            vec![
+                vec!["foo", "main", "Bounded { width: 4 }", "10", "12", "2", "7"],
+                vec!["foo", "helper", "N/A", "N/A", "N/A", "N/A", "7"],
+            ]
+        );
+
+        let report = InfoReport { programs: vec![program_info()] };
+        assert_eq!(
+            serde_json::to_value(&report).unwrap(),
+            json!({
+                "programs": [{
+                    "package_name": "foo",
+                    "functions": [
+                        { "name": "main", "opcodes": 10, "witnesses": 12, "public_inputs": 2 },
+                    ],
+                    "unconstrained_functions": [{ "name": "helper", "opcodes": 7 }],
+                }],
+            })
+        );
+    }
+}
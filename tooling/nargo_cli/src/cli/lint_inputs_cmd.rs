@@ -0,0 +1,97 @@
+use clap::Args;
+use nargo::constants::VERIFIER_INPUT_FILE;
+use nargo::package::CrateName;
+use nargo_toml::{get_package_manifest, resolve_workspace_from_toml, PackageSelection};
+use noirc_abi::input_parser::{lint_toml, Format, LintSeverity};
+use noirc_driver::{CompileOptions, NOIR_ARTIFACT_VERSION_STRING};
+
+use super::compile_cmd::compile_workspace_full;
+use super::fs::program::read_program_from_file;
+use super::NargoConfig;
+use crate::errors::{CliError, FilesystemError};
+
+/// Checks a verifier toml file for common mistakes, such as missing parameters or values
+/// which don't fit their parameter's type
+#[derive(Debug, Clone, Args)]
+pub(crate) struct LintInputsCommand {
+    /// The name of the toml file which contains the inputs for the verifier
+    #[clap(long, short, default_value = VERIFIER_INPUT_FILE)]
+    verifier_name: String,
+
+    /// Lint the file against all of the program's parameters rather than only the public ones,
+    /// e.g. to lint a prover toml file
+    #[clap(long)]
+    all_parameters: bool,
+
+    /// The name of the package to lint the inputs of
+    #[clap(long, conflicts_with = "workspace")]
+    package: Option<CrateName>,
+
+    /// Lint the inputs of all packages in the workspace
+    #[clap(long, conflicts_with = "package")]
+    workspace: bool,
+
+    #[clap(flatten)]
+    compile_options: CompileOptions,
+}
+
+pub(crate) fn run(args: LintInputsCommand, config: NargoConfig) -> Result<(), CliError> {
+    let toml_path = get_package_manifest(&config.program_dir)?;
+    let default_selection =
+        if args.workspace { PackageSelection::All } else { PackageSelection::DefaultOrAll };
+    let selection = args.package.map_or(default_selection, PackageSelection::Selected);
+    let workspace = resolve_workspace_from_toml(
+        &toml_path,
+        selection,
+        Some(NOIR_ARTIFACT_VERSION_STRING.to_string()),
+    )?;
+
+    // Compile the full workspace in order to generate the ABIs of its binary packages.
+    compile_workspace_full(&workspace, &args.compile_options)?;
+
+    let mut error_count = 0;
+    let binary_packages = workspace.into_iter().filter(|package| package.is_binary());
+    for package in binary_packages {
+        let program_artifact_path = workspace.package_build_path(package);
+        let abi = read_program_from_file(program_artifact_path)?.abi;
+        let abi = if args.all_parameters { abi } else { abi.public_abi() };
+
+        let file_path =
+            package.root_dir.join(&args.verifier_name).with_extension(Format::Toml.ext());
+        // As when reading inputs, the file isn't needed if there are no inputs to give, and
+        // giving the return value is optional.
+        if abi.is_empty() || (abi.parameters.is_empty() && !file_path.exists()) {
+            println!("[{}] No inputs to lint", package.name);
+            continue;
+        }
+
+        let input_string = std::fs::read_to_string(&file_path).map_err(|_| {
+            FilesystemError::MissingTomlFile(args.verifier_name.clone(), file_path.clone())
+        })?;
+        let lints = lint_toml(&input_string, &abi).map_err(FilesystemError::from)?;
+
+        for lint in &lints {
+            let severity = match lint.severity {
+                LintSeverity::Warning => "warning",
+                LintSeverity::Error => {
+                    error_count += 1;
+                    "error"
+                }
+            };
+            eprintln!(
+                "[{}] {severity}: `{}`: {} ({})",
+                package.name, lint.parameter, lint.message, lint.suggestion
+            );
+        }
+
+        if lints.is_empty() {
+            println!("[{}] No problems found in {}.toml", package.name, args.verifier_name);
+        }
+    }
+
+    if error_count == 0 {
+        Ok(())
+    } else {
+        Err(CliError::Generic(format!("Found {error_count} error(s) in the input files")))
+    }
+}
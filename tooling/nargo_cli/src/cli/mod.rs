@@ -9,6 +9,7 @@ use color_eyre::eyre;
 mod fs;
 
 mod check_cmd;
+mod check_inputs_cmd;
 mod compile_cmd;
 mod dap_cmd;
 mod debug_cmd;
@@ -18,6 +19,7 @@ mod fmt_cmd;
 mod generate_completion_script_cmd;
 mod info_cmd;
 mod init_cmd;
+mod lint_inputs_cmd;
 mod lsp_cmd;
 mod new_cmd;
 mod test_cmd;
@@ -56,6 +58,7 @@ pub(crate) struct NargoConfig {
 #[derive(Subcommand, Clone, Debug)]
 enum NargoCommand {
     Check(check_cmd::CheckCommand),
+    CheckInputs(check_inputs_cmd::CheckInputsCommand),
     Fmt(fmt_cmd::FormatCommand),
     #[command(alias = "build")]
     Compile(compile_cmd::CompileCommand),
@@ -67,6 +70,7 @@ enum NargoCommand {
     Debug(debug_cmd::DebugCommand),
     Test(test_cmd::TestCommand),
     Info(info_cmd::InfoCommand),
+    LintInputs(lint_inputs_cmd::LintInputsCommand),
     Lsp(lsp_cmd::LspCommand),
     #[command(hide = true)]
     Dap(dap_cmd::DapCommand),
@@ -85,13 +89,15 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
     // Search through parent directories to find package root if necessary.
     match &command {
         NargoCommand::Check(..)
+        | NargoCommand::CheckInputs(..)
         | NargoCommand::Fmt(..)
         | NargoCommand::Compile(..)
         | NargoCommand::Execute(..)
         | NargoCommand::Export(..)
         | NargoCommand::Debug(..)
         | NargoCommand::Test(..)
-        | NargoCommand::Info(..) => {
+        | NargoCommand::Info(..)
+        | NargoCommand::LintInputs(..) => {
             config.program_dir = find_package_root(&config.program_dir)?;
         }
         NargoCommand::New(..)
@@ -105,12 +111,14 @@ pub(crate) fn start_cli() -> eyre::Result<()> {
         NargoCommand::New(args) => new_cmd::run(args, config),
         NargoCommand::Init(args) => init_cmd::run(args, config),
         NargoCommand::Check(args) => check_cmd::run(args, config),
+        NargoCommand::CheckInputs(args) => check_inputs_cmd::run(args, config),
         NargoCommand::Compile(args) => compile_cmd::run(args, config),
         NargoCommand::Debug(args) => debug_cmd::run(args, config),
         NargoCommand::Execute(args) => execute_cmd::run(args, config),
         NargoCommand::Export(args) => export_cmd::run(args, config),
         NargoCommand::Test(args) => test_cmd::run(args, config),
         NargoCommand::Info(args) => info_cmd::run(args, config),
+        NargoCommand::LintInputs(args) => lint_inputs_cmd::run(args, config),
         NargoCommand::Lsp(args) => lsp_cmd::run(args, config),
         NargoCommand::Dap(args) => dap_cmd::run(args, config),
         NargoCommand::Fmt(args) => fmt_cmd::run(args, config),
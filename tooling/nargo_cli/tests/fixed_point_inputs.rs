@@ -0,0 +1,43 @@
+//! This integration test checks that `nargo execute --fixed-point-scale` reads decimal inputs
+//! from the prover toml file as fixed-point values.
+
+use assert_cmd::prelude::*;
+use std::process::Command;
+
+use assert_fs::prelude::{FileWriteStr, PathChild};
+
+#[test]
+fn execute_with_fixed_point_inputs() {
+    let test_dir = assert_fs::TempDir::new().unwrap();
+
+    let project_name = "fixed_point";
+    let project_dir = test_dir.child(project_name);
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&test_dir).arg("new").arg(project_name);
+    cmd.assert().success();
+
+    // With 16 fractional bits, 1.5 is 98304 and 0.75 is 49152.
+    project_dir
+        .child("src")
+        .child("main.nr")
+        .write_str("fn main(x: Field, y: pub u32) { assert(x == 98304); assert(y == 49152); }")
+        .unwrap();
+    project_dir.child("Prover.toml").write_str("x = \"1.5\"\ny = \"0.75\"").unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&project_dir).arg("execute").arg("--fixed-point-scale").arg("16");
+    cmd.assert().success();
+
+    // Decimal values are rejected unless a scale is given.
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&project_dir).arg("execute");
+    cmd.assert().failure();
+
+    // Bare TOML floats are rejected even with a scale, as they can't be converted exactly.
+    project_dir.child("Prover.toml").write_str("x = 1.5\ny = \"0.75\"").unwrap();
+
+    let mut cmd = Command::cargo_bin("nargo").unwrap();
+    cmd.current_dir(&project_dir).arg("execute").arg("--fixed-point-scale").arg("16");
+    cmd.assert().failure();
+}
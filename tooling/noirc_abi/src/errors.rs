@@ -17,6 +17,12 @@ pub enum InputParserError {
     AbiTypeMismatch(AbiType),
     #[error("Expected argument `{0}`, but none was found")]
     MissingArgument(String),
+    #[error("Input references undefined constant `{0}`")]
+    UndefinedConstant(String),
+    #[error("Constant `{0}` is defined in terms of itself")]
+    CyclicConstant(String),
+    #[error("Input references constant `{0}`, but `constants` is a parameter of the program so the file has no constants table")]
+    ConstantsParameterConflict(String),
 }
 
 impl From<toml::ser::Error> for InputParserError {
@@ -53,6 +53,53 @@ impl InputTypecheckingError {
             | InputTypecheckingError::IncompatibleTypes { path, .. } => path,
         }
     }
+
+    /// A suggestion for how to fix the input value.
+    pub(crate) fn suggestion(&self) -> String {
+        match self {
+            InputTypecheckingError::OutsideOfValidRange { .. } => {
+                "use a value which fits in the parameter's type".to_owned()
+            }
+            InputTypecheckingError::LengthMismatch { expected_length, .. } => {
+                format!("provide exactly {expected_length} elements")
+            }
+            InputTypecheckingError::MissingField { expected_field, .. } => {
+                format!("add a value for the field `{expected_field}`")
+            }
+            InputTypecheckingError::UnexpectedField { extra_field, .. } => {
+                format!("remove the field `{extra_field}`")
+            }
+            InputTypecheckingError::IncompatibleTypes { .. } => {
+                "provide a value of the parameter's type".to_owned()
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LintSeverity {
+    Warning,
+    Error,
+}
+
+/// A problem with a value in an input file, found by [lint_toml].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputLint {
+    pub severity: LintSeverity,
+    /// The parameter the problem was found in, or the path to a value within it (e.g. `foo.bar[1]`).
+    pub parameter: String,
+    pub message: String,
+    pub suggestion: String,
+}
+
+/// Checks every value of a TOML input file against `abi`, returning all problems found rather
+/// than stopping at the first one like [Format::parse] does.
+///
+/// Missing parameters and values which don't match their parameter's type are errors, while
+/// values for names which aren't parameters of the program are warnings. An `Err` is only
+/// returned if the file can't be parsed as TOML at all.
+pub fn lint_toml(input_string: &str, abi: &Abi) -> Result<Vec<InputLint>, InputParserError> {
+    toml::lint_toml(input_string, abi)
 }
 
 impl InputValue {
@@ -209,7 +256,30 @@ impl Format {
     ) -> Result<BTreeMap<String, InputValue>, InputParserError> {
         match self {
             Format::Json => json::parse_json(input_string, abi),
-            Format::Toml => toml::parse_toml(input_string, abi),
+            Format::Toml => toml::parse_toml(input_string, abi, None),
+        }
+    }
+
+    /// Parses `input_string` like [Format::parse], additionally accepting decimal strings such as
+    /// `"1.5"` for fields and unsigned integers. These are encoded as fixed-point numbers with
+    /// `scale_bits` fractional bits, i.e. multiplied by `2^scale_bits`, and must be a whole
+    /// number once scaled. Values without a decimal point are parsed as usual.
+    ///
+    /// Decimals must be quoted so that they're converted exactly: bare TOML floats such as
+    /// `1.5` are rejected, as they would lose precision past about 15 significant digits.
+    ///
+    /// Fixed-point values are only supported in TOML inputs.
+    pub fn parse_fixed_point(
+        &self,
+        input_string: &str,
+        abi: &Abi,
+        scale_bits: u32,
+    ) -> Result<BTreeMap<String, InputValue>, InputParserError> {
+        match self {
+            Format::Json => Err(InputParserError::ParseInputMap(
+                "fixed-point values are only supported in toml files".to_owned(),
+            )),
+            Format::Toml => toml::parse_toml(input_string, abi, Some(scale_bits)),
         }
     }
 
@@ -298,12 +368,27 @@ mod serialization_tests {
     }
 }
 
-fn parse_str_to_field(value: &str) -> Result<FieldElement, InputParserError> {
-    let big_num = if let Some(hex) = value.strip_prefix("0x") {
-        BigUint::from_str_radix(hex, 16)
+/// Splits a numeric string into its digits and their radix, as given by a `0x`, `0o` or `0b` prefix.
+/// Strings without any of these prefixes are treated as decimal.
+fn split_radix_prefix(value: &str) -> (&str, u32) {
+    if let Some(hex) = value.strip_prefix("0x") {
+        (hex, 16)
+    } else if let Some(octal) = value.strip_prefix("0o") {
+        (octal, 8)
+    } else if let Some(binary) = value.strip_prefix("0b") {
+        (binary, 2)
     } else {
-        BigUint::from_str_radix(value, 10)
-    };
+        (value, 10)
+    }
+}
+
+/// Parses a string value into a field element.
+///
+/// The radix is determined by the value alone: a `0x`, `0o` or `0b` prefix selects hexadecimal,
+/// octal or binary, and any other value is decimal. Digits may be separated by underscores.
+fn parse_str_to_field(value: &str) -> Result<FieldElement, InputParserError> {
+    let (digits, radix) = split_radix_prefix(value);
+    let big_num = BigUint::from_str_radix(digits, radix);
     big_num.map_err(|err_msg| InputParserError::ParseStr(err_msg.to_string())).and_then(|bigint| {
         if bigint < FieldElement::modulus() {
             Ok(field_from_big_uint(bigint))
@@ -317,11 +402,8 @@ fn parse_str_to_field(value: &str) -> Result<FieldElement, InputParserError> {
 }
 
 fn parse_str_to_signed(value: &str, width: u32) -> Result<FieldElement, InputParserError> {
-    let big_num = if let Some(hex) = value.strip_prefix("0x") {
-        BigInt::from_str_radix(hex, 16)
-    } else {
-        BigInt::from_str_radix(value, 10)
-    };
+    let (digits, radix) = split_radix_prefix(value);
+    let big_num = BigInt::from_str_radix(digits, radix);
 
     big_num.map_err(|err_msg| InputParserError::ParseStr(err_msg.to_string())).and_then(|bigint| {
         let modulus: BigInt = FieldElement::modulus().into();
@@ -341,6 +423,37 @@ fn parse_str_to_signed(value: &str, width: u32) -> Result<FieldElement, InputPar
     })
 }
 
+/// Parses a decimal string such as `1.5` into a fixed-point field element with `scale_bits`
+/// fractional bits, rejecting values which aren't a whole number after scaling.
+fn parse_fixed_point_str_to_field(
+    value: &str,
+    scale_bits: u32,
+) -> Result<FieldElement, InputParserError> {
+    let (whole, fraction) = value.split_once('.').unwrap_or((value, ""));
+    let fraction = fraction.replace('_', "");
+
+    let digits = BigUint::from_str_radix(&format!("{whole}{fraction}"), 10)
+        .map_err(|err_msg| InputParserError::ParseStr(err_msg.to_string()))?;
+    let numerator = digits << scale_bits;
+    let denominator = BigUint::from(10u32).pow(fraction.len() as u32);
+
+    if !(&numerator % &denominator).is_zero() {
+        return Err(InputParserError::ParseStr(format!(
+            "Input {value} is not a whole number when scaled by 2^{scale_bits}"
+        )));
+    }
+
+    let scaled = numerator / denominator;
+    if scaled < FieldElement::modulus() {
+        Ok(field_from_big_uint(scaled))
+    } else {
+        Err(InputParserError::ParseStr(format!(
+            "Input exceeds field modulus. Values must fall within [0, {})",
+            FieldElement::modulus(),
+        )))
+    }
+}
+
 fn field_from_big_uint(bigint: BigUint) -> FieldElement {
     FieldElement::from_be_bytes_reduce(&bigint.to_bytes_be())
 }
@@ -362,7 +475,7 @@ mod test {
     use acvm::{AcirField, FieldElement};
     use num_bigint::BigUint;
 
-    use super::parse_str_to_field;
+    use super::{parse_fixed_point_str_to_field, parse_str_to_field, parse_str_to_signed};
 
     fn big_uint_from_field(field: FieldElement) -> BigUint {
         BigUint::from_bytes_be(&field.to_be_bytes())
@@ -395,9 +508,67 @@ mod test {
         }
     }
 
+    #[test]
+    fn parse_fields_from_octal_and_binary_strings() {
+        let expected = FieldElement::from(0o755u128);
+        assert_eq!(parse_str_to_field("0o755").unwrap(), expected);
+        assert_eq!(parse_str_to_field("0b111101101").unwrap(), expected);
+        assert_eq!(parse_str_to_field("0b1_1110_1101").unwrap(), expected);
+
+        assert_eq!(parse_str_to_field("0b0").unwrap(), FieldElement::zero());
+        assert_eq!(parse_str_to_field("0o1").unwrap(), FieldElement::one());
+    }
+
+    #[test]
+    fn rejects_invalid_octal_and_binary_digits() {
+        assert!(parse_str_to_field("0b102").is_err());
+        assert!(parse_str_to_field("0o758").is_err());
+        assert!(parse_str_to_field("0b").is_err());
+        assert!(parse_str_to_signed("0b2", 8).is_err());
+    }
+
+    #[test]
+    fn parse_signed_from_octal_and_binary_strings() {
+        assert_eq!(parse_str_to_signed("0o17", 8).unwrap(), FieldElement::from(15u128));
+        assert_eq!(parse_str_to_signed("0b1111", 8).unwrap(), FieldElement::from(15u128));
+    }
+
     #[test]
     fn rejects_noncanonical_fields() {
         let noncanonical_field = FieldElement::modulus().to_string();
         assert!(parse_str_to_field(&noncanonical_field).is_err());
     }
+
+    #[test]
+    fn equivalent_representations_parse_to_the_same_field() {
+        // Decimal, hex and underscore-separated strings must all collapse into the same
+        // `FieldElement` so that they produce identical `InputValue`s.
+        let expected = FieldElement::from(1_000_000u128);
+        for representation in ["1000000", "1_000_000", "0xf4240", "0x0f_42_40"] {
+            assert_eq!(parse_str_to_field(representation).unwrap(), expected, "{representation}");
+        }
+    }
+
+    #[test]
+    fn parse_exact_fixed_point_values() {
+        assert_eq!(
+            parse_fixed_point_str_to_field("1.5", 16).unwrap(),
+            FieldElement::from(98304u128)
+        );
+        assert_eq!(parse_fixed_point_str_to_field("0.25", 2).unwrap(), FieldElement::one());
+        assert_eq!(parse_fixed_point_str_to_field("3", 4).unwrap(), FieldElement::from(48u128));
+        assert_eq!(parse_fixed_point_str_to_field("2.", 1).unwrap(), FieldElement::from(4u128));
+        assert_eq!(
+            parse_fixed_point_str_to_field("1_000.2_5", 2).unwrap(),
+            FieldElement::from(4001u128)
+        );
+    }
+
+    #[test]
+    fn rejects_inexact_fixed_point_values() {
+        assert!(parse_fixed_point_str_to_field("0.1", 16).is_err());
+        assert!(parse_fixed_point_str_to_field("0.125", 2).is_err());
+        assert!(parse_fixed_point_str_to_field("-1.5", 16).is_err());
+        assert!(parse_fixed_point_str_to_field(".", 16).is_err());
+    }
 }
@@ -1,16 +1,24 @@
-use super::{parse_str_to_field, parse_str_to_signed, InputValue};
+use super::{
+    parse_fixed_point_str_to_field, parse_str_to_field, parse_str_to_signed, InputLint, InputValue,
+    LintSeverity,
+};
 use crate::{errors::InputParserError, Abi, AbiType, MAIN_RETURN_NAME};
 use acvm::{AcirField, FieldElement};
 use iter_extended::{try_btree_map, try_vecmap};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
+/// The name of an optional table of constants which other values in the file can reference.
+const CONSTANTS_TABLE: &str = "constants";
+
 pub(crate) fn parse_toml(
     input_string: &str,
     abi: &Abi,
+    fixed_point_scale: Option<u32>,
 ) -> Result<BTreeMap<String, InputValue>, InputParserError> {
     // Parse input.toml into a BTreeMap.
-    let data: BTreeMap<String, TomlTypes> = toml::from_str(input_string)?;
+    let mut data: BTreeMap<String, TomlTypes> = toml::from_str(input_string)?;
+    substitute_constants(&mut data, abi)?;
 
     // Convert arguments to field elements.
     let mut parsed_inputs = try_btree_map(abi.to_btree_map(), |(arg_name, abi_type)| {
@@ -19,7 +27,7 @@ pub(crate) fn parse_toml(
             .get(&arg_name)
             .ok_or_else(|| InputParserError::MissingArgument(arg_name.clone()))?;
 
-        InputValue::try_from_toml(value.clone(), &abi_type, &arg_name)
+        InputValue::try_from_toml(value.clone(), &abi_type, &arg_name, fixed_point_scale)
             .map(|input_value| (arg_name, input_value))
     })?;
 
@@ -32,6 +40,7 @@ pub(crate) fn parse_toml(
             toml_return_value.clone(),
             &return_type.abi_type,
             MAIN_RETURN_NAME,
+            fixed_point_scale,
         )?;
         parsed_inputs.insert(MAIN_RETURN_NAME.to_owned(), return_value);
     }
@@ -39,6 +48,162 @@ pub(crate) fn parse_toml(
     Ok(parsed_inputs)
 }
 
+pub(crate) fn lint_toml(input_string: &str, abi: &Abi) -> Result<Vec<InputLint>, InputParserError> {
+    let mut data: BTreeMap<String, TomlTypes> = toml::from_str(input_string)?;
+    substitute_constants(&mut data, abi)?;
+
+    let mut lints = Vec::new();
+    let return_type =
+        abi.return_type.as_ref().map(|typ| (MAIN_RETURN_NAME.to_owned(), typ.abi_type.clone()));
+    for (arg_name, abi_type) in abi.to_btree_map().into_iter().chain(return_type) {
+        let Some(value) = data.remove(&arg_name) else {
+            // Providing the return value is optional.
+            if arg_name != MAIN_RETURN_NAME {
+                lints.push(InputLint {
+                    severity: LintSeverity::Error,
+                    message: format!("Expected argument `{arg_name}`, but none was found"),
+                    suggestion: format!("add a value for `{arg_name}`"),
+                    parameter: arg_name,
+                });
+            }
+            continue;
+        };
+
+        match InputValue::try_from_toml(value, &abi_type, &arg_name, None) {
+            Ok(input_value) => {
+                if let Err(error) = input_value.find_type_mismatch(&abi_type, arg_name) {
+                    lints.push(InputLint {
+                        severity: LintSeverity::Error,
+                        parameter: error.path().to_owned(),
+                        message: error.to_string(),
+                        suggestion: error.suggestion(),
+                    });
+                }
+            }
+            Err(InputParserError::MissingArgument(field)) => lints.push(InputLint {
+                severity: LintSeverity::Error,
+                message: format!("Expected field `{field}`, but none was found"),
+                suggestion: format!("add a value for `{field}`"),
+                parameter: field,
+            }),
+            Err(error) => lints.push(InputLint {
+                severity: LintSeverity::Error,
+                parameter: arg_name,
+                message: error.to_string(),
+                suggestion: "provide a value of the parameter's type".to_owned(),
+            }),
+        }
+    }
+
+    for unused_name in data.into_keys() {
+        lints.push(InputLint {
+            severity: LintSeverity::Warning,
+            message: format!("`{unused_name}` is not a parameter of the program"),
+            suggestion: "remove it, or check the parameter's name for typos".to_owned(),
+            parameter: unused_name,
+        });
+    }
+
+    Ok(lints)
+}
+
+/// Replaces every reference to a constant in `data` with the constant's value.
+///
+/// If the file contains a `[constants]` table, any string value of the form `"$NAME"` given for a
+/// parameter (or the return value) is replaced by the value of the constant `NAME` before it's
+/// converted to an [InputValue], and so are references within that constant's value. Values are
+/// only substituted where the ABI expects something other than a string, so a `str` input such as
+/// `"$5"` is left as is. References to constants which don't exist, or which depend on
+/// themselves, are errors, including any reference in a file without a `[constants]` table.
+///
+/// If the ABI has a parameter named `constants`, the file's `constants` entry is that parameter's
+/// value rather than a table of constants, so any reference to a constant is an error.
+fn substitute_constants(
+    data: &mut BTreeMap<String, TomlTypes>,
+    abi: &Abi,
+) -> Result<(), InputParserError> {
+    let constants = if abi.parameters.iter().any(|param| param.name == CONSTANTS_TABLE) {
+        None
+    } else {
+        match data.remove(CONSTANTS_TABLE) {
+            Some(TomlTypes::Table(constants)) => Some(constants),
+            Some(_) => {
+                return Err(InputParserError::ParseInputMap(format!(
+                    "`{CONSTANTS_TABLE}` must be a table"
+                )))
+            }
+            // References are still resolved, so that they're reported as undefined constants.
+            None => Some(BTreeMap::new()),
+        }
+    };
+
+    let return_type =
+        abi.return_type.as_ref().map(|typ| (MAIN_RETURN_NAME.to_owned(), typ.abi_type.clone()));
+    for (arg_name, abi_type) in abi.to_btree_map().into_iter().chain(return_type) {
+        if let Some(value) = data.get_mut(&arg_name) {
+            *value =
+                substitute_value(value.clone(), &abi_type, constants.as_ref(), &mut Vec::new())?;
+        }
+    }
+    Ok(())
+}
+
+/// `resolving` holds the names of the constants whose values are currently being substituted.
+fn substitute_value(
+    value: TomlTypes,
+    abi_type: &AbiType,
+    constants: Option<&BTreeMap<String, TomlTypes>>,
+    resolving: &mut Vec<String>,
+) -> Result<TomlTypes, InputParserError> {
+    match (value, abi_type) {
+        (TomlTypes::String(string), _)
+            if string.starts_with('$') && !matches!(abi_type, AbiType::String { .. }) =>
+        {
+            let name = &string[1..];
+            let constants = constants
+                .ok_or_else(|| InputParserError::ConstantsParameterConflict(name.to_owned()))?;
+            let constant = constants
+                .get(name)
+                .ok_or_else(|| InputParserError::UndefinedConstant(name.to_owned()))?;
+            if resolving.iter().any(|resolving_name| resolving_name == name) {
+                return Err(InputParserError::CyclicConstant(name.to_owned()));
+            }
+
+            resolving.push(name.to_owned());
+            let value = substitute_value(constant.clone(), abi_type, Some(constants), resolving)?;
+            resolving.pop();
+            Ok(value)
+        }
+        (TomlTypes::Array(array), AbiType::Array { typ, .. }) => {
+            let array =
+                try_vecmap(array, |value| substitute_value(value, typ, constants, resolving))?;
+            Ok(TomlTypes::Array(array))
+        }
+        (TomlTypes::Array(array), AbiType::Tuple { fields }) => {
+            // Extra elements are left as is, to be reported when the value is converted.
+            let array = try_vecmap(array.into_iter().enumerate(), |(index, value)| {
+                match fields.get(index) {
+                    Some(typ) => substitute_value(value, typ, constants, resolving),
+                    None => Ok(value),
+                }
+            })?;
+            Ok(TomlTypes::Array(array))
+        }
+        (TomlTypes::Table(table), AbiType::Struct { fields, .. }) => {
+            let table = try_btree_map(table, |(key, value)| {
+                let field_type = fields.iter().find(|(name, _)| *name == key);
+                let value = match field_type {
+                    Some((_, typ)) => substitute_value(value, typ, constants, resolving),
+                    None => Ok(value),
+                };
+                value.map(|value| (key, value))
+            })?;
+            Ok(TomlTypes::Table(table))
+        }
+        (value, _) => Ok(value),
+    }
+}
+
 pub(crate) fn serialize_to_toml(
     input_map: &BTreeMap<String, InputValue>,
     abi: &Abi,
@@ -123,9 +288,17 @@ impl InputValue {
         value: TomlTypes,
         param_type: &AbiType,
         arg_name: &str,
+        fixed_point_scale: Option<u32>,
     ) -> Result<InputValue, InputParserError> {
         let input_value = match (value, param_type) {
             (TomlTypes::String(string), AbiType::String { .. }) => InputValue::String(string),
+            (
+                TomlTypes::String(string),
+                AbiType::Field | AbiType::Integer { sign: crate::Sign::Unsigned, .. },
+            ) if fixed_point_scale.is_some() && string.contains('.') => {
+                let scale_bits = fixed_point_scale.unwrap();
+                InputValue::Field(parse_fixed_point_str_to_field(&string, scale_bits)?)
+            }
             (
                 TomlTypes::String(string),
                 AbiType::Field
@@ -147,8 +320,9 @@ impl InputValue {
             (TomlTypes::Bool(boolean), AbiType::Boolean) => InputValue::Field(boolean.into()),
 
             (TomlTypes::Array(array), AbiType::Array { typ, .. }) => {
-                let array_elements =
-                    try_vecmap(array, |value| InputValue::try_from_toml(value, typ, arg_name))?;
+                let array_elements = try_vecmap(array, |value| {
+                    InputValue::try_from_toml(value, typ, arg_name, fixed_point_scale)
+                })?;
                 InputValue::Vec(array_elements)
             }
 
@@ -159,7 +333,7 @@ impl InputValue {
                     let value = table
                         .get(field_name)
                         .ok_or_else(|| InputParserError::MissingArgument(field_id.clone()))?;
-                    InputValue::try_from_toml(value.clone(), abi_type, &field_id)
+                    InputValue::try_from_toml(value.clone(), abi_type, &field_id, fixed_point_scale)
                         .map(|input_value| (field_name.to_string(), input_value))
                 })?;
 
@@ -168,7 +342,7 @@ impl InputValue {
 
             (TomlTypes::Array(array), AbiType::Tuple { fields }) => {
                 let tuple_fields = try_vecmap(array.into_iter().zip(fields), |(value, typ)| {
-                    InputValue::try_from_toml(value, typ, arg_name)
+                    InputValue::try_from_toml(value, typ, arg_name, fixed_point_scale)
                 })?;
                 InputValue::Vec(tuple_fields)
             }
@@ -179,3 +353,207 @@ impl InputValue {
         Ok(input_value)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use acvm::FieldElement;
+
+    use super::{lint_toml, parse_toml};
+    use crate::{
+        errors::InputParserError,
+        input_parser::{InputValue, LintSeverity},
+        Abi, AbiParameter, AbiType, AbiVisibility, Sign,
+    };
+
+    fn abi() -> Abi {
+        let param = |name: &str, typ| AbiParameter {
+            name: name.into(),
+            typ,
+            visibility: AbiVisibility::Public,
+        };
+        Abi {
+            parameters: vec![
+                param("x", AbiType::Field),
+                param(
+                    "y",
+                    AbiType::Array {
+                        length: 2,
+                        typ: Box::new(AbiType::Integer { sign: Sign::Unsigned, width: 32 }),
+                    },
+                ),
+            ],
+            return_type: None,
+            error_types: Default::default(),
+        }
+    }
+
+    #[test]
+    fn parses_mixed_number_representations() {
+        let input = "
+        x = \"0x0f_42_40\"
+        y = [1_000_000, \"1_000_000\"]
+        ";
+        let inputs = parse_toml(input, &abi(), None).unwrap();
+        let million = InputValue::Field(FieldElement::from(1_000_000u128));
+        assert_eq!(inputs["x"], million);
+        assert_eq!(inputs["y"], InputValue::Vec(vec![million.clone(), million]));
+    }
+
+    #[test]
+    fn parses_fixed_point_values() {
+        let inputs = parse_toml("x = \"1.5\"\ny = [\"0.75\", 3]", &abi(), Some(16)).unwrap();
+        assert_eq!(inputs["x"], InputValue::Field(FieldElement::from(98304u128)));
+        assert_eq!(
+            inputs["y"],
+            InputValue::Vec(vec![
+                InputValue::Field(FieldElement::from(49152u128)),
+                InputValue::Field(FieldElement::from(3u128)),
+            ])
+        );
+
+        // Decimal strings are converted exactly, however many digits they have.
+        let inputs =
+            parse_toml("x = \"12345678901234567890.5\"\ny = [1, 2]", &abi(), Some(1)).unwrap();
+        assert_eq!(
+            inputs["x"],
+            InputValue::Field(FieldElement::from(24_691_357_802_469_135_781u128))
+        );
+    }
+
+    #[test]
+    fn rejects_inexact_fixed_point_values() {
+        assert!(parse_toml("x = \"0.1\"\ny = [1, 2]", &abi(), Some(16)).is_err());
+        assert!(parse_toml("x = \"0.125\"\ny = [1, 2]", &abi(), Some(2)).is_err());
+    }
+
+    #[test]
+    fn rejects_bare_decimal_values() {
+        assert!(parse_toml("x = 1.5\ny = [1, 2]", &abi(), Some(16)).is_err());
+    }
+
+    #[test]
+    fn rejects_decimal_values_without_a_scale() {
+        assert!(parse_toml("x = \"1.5\"\ny = [1, 2]", &abi(), None).is_err());
+    }
+
+    #[test]
+    fn substitutes_constants() {
+        let input = "
+        x = \"$HALF\"
+        y = [\"$ONE\", 7]
+
+        [constants]
+        ONE = 1
+        HALF = \"$ALSO_HALF\"
+        ALSO_HALF = \"0x10\"
+        ";
+        let inputs = parse_toml(input, &abi(), None).unwrap();
+        assert_eq!(inputs["x"], InputValue::Field(FieldElement::from(16u128)));
+        assert_eq!(
+            inputs["y"],
+            InputValue::Vec(vec![
+                InputValue::Field(FieldElement::from(1u128)),
+                InputValue::Field(FieldElement::from(7u128)),
+            ])
+        );
+    }
+
+    #[test]
+    fn rejects_undefined_and_cyclic_constants() {
+        let undefined = "x = \"$MISSING\"\ny = [1, 2]\n[constants]\nA = 1";
+        assert!(matches!(
+            parse_toml(undefined, &abi(), None),
+            Err(InputParserError::UndefinedConstant(name)) if name == "MISSING"
+        ));
+
+        let cyclic = "x = \"$A\"\ny = [1, 2]\n[constants]\nA = \"$B\"\nB = \"$A\"";
+        assert!(matches!(
+            parse_toml(cyclic, &abi(), None),
+            Err(InputParserError::CyclicConstant(_))
+        ));
+    }
+
+    #[test]
+    fn rejects_references_without_a_constants_table() {
+        assert!(matches!(
+            parse_toml("x = \"$FOO\"\ny = [1, 2]", &abi(), None),
+            Err(InputParserError::UndefinedConstant(name)) if name == "FOO"
+        ));
+        assert!(matches!(
+            lint_toml("x = 1\ny = [\"$FOO\", 2]", &abi()),
+            Err(InputParserError::UndefinedConstant(name)) if name == "FOO"
+        ));
+    }
+
+    #[test]
+    fn leaves_string_inputs_starting_with_a_dollar() {
+        let abi = Abi {
+            parameters: vec![
+                AbiParameter {
+                    name: "price".into(),
+                    typ: AbiType::String { length: 2 },
+                    visibility: AbiVisibility::Public,
+                },
+                AbiParameter {
+                    name: "x".into(),
+                    typ: AbiType::Field,
+                    visibility: AbiVisibility::Public,
+                },
+            ],
+            return_type: None,
+            error_types: Default::default(),
+        };
+        let input = "price = \"$5\"\nx = \"$FIVE\"\n[constants]\nFIVE = 5";
+        let inputs = parse_toml(input, &abi, None).unwrap();
+        assert_eq!(inputs["price"], InputValue::String("$5".to_owned()));
+        assert_eq!(inputs["x"], InputValue::Field(FieldElement::from(5u128)));
+    }
+
+    #[test]
+    fn rejects_references_when_constants_is_a_parameter() {
+        let param = |name: &str| AbiParameter {
+            name: name.into(),
+            typ: AbiType::Field,
+            visibility: AbiVisibility::Public,
+        };
+        let abi = Abi {
+            parameters: vec![param("constants"), param("x")],
+            return_type: None,
+            error_types: Default::default(),
+        };
+
+        let inputs = parse_toml("constants = 3\nx = 1", &abi, None).unwrap();
+        assert_eq!(inputs["constants"], InputValue::Field(FieldElement::from(3u128)));
+
+        assert!(matches!(
+            parse_toml("constants = 3\nx = \"$A\"", &abi, None),
+            Err(InputParserError::ConstantsParameterConflict(name)) if name == "A"
+        ));
+    }
+
+    #[test]
+    fn lints_every_problem() {
+        let input = "
+        y = [1, 2, 3]
+        z = 4
+        ";
+        let lints = lint_toml(input, &abi()).unwrap();
+        let problems: Vec<_> =
+            lints.iter().map(|lint| (lint.severity, lint.parameter.as_str())).collect();
+        assert_eq!(
+            problems,
+            vec![
+                (LintSeverity::Error, "x"),
+                (LintSeverity::Error, "y"),
+                (LintSeverity::Warning, "z"),
+            ]
+        );
+        assert_eq!(lints[1].suggestion, "provide exactly 2 elements");
+
+        let lints = lint_toml("x = \"0xg\"\ny = [1, 4294967296]", &abi()).unwrap();
+        let parameters: Vec<_> = lints.iter().map(|lint| lint.parameter.as_str()).collect();
+        assert_eq!(parameters, vec!["x", "y[1]"]);
+
+        assert!(lint_toml("x = 1\ny = [1, 2]", &abi()).unwrap().is_empty());
+    }
+}
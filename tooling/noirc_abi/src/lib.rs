@@ -210,6 +210,16 @@ impl Abi {
         self.return_type.is_none() && self.parameters.is_empty()
     }
 
+    /// Returns an ABI containing only the public parameters and return value of this ABI.
+    #[must_use]
+    pub fn public_abi(self) -> Abi {
+        let parameters = self.parameters.into_iter().filter(|param| param.is_public()).collect();
+        let return_type = self
+            .return_type
+            .filter(|return_type| matches!(return_type.visibility, AbiVisibility::Public));
+        Abi { parameters, return_type, error_types: self.error_types }
+    }
+
     pub fn to_btree_map(&self) -> BTreeMap<String, AbiType> {
         let mut map = BTreeMap::new();
         for param in self.parameters.iter() {